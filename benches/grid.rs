@@ -0,0 +1,72 @@
+//! パラメータグリッドの探索の比較
+//!
+//! ```sh
+//! cargo bench --bench grid
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use jgd::{GridBuf, LatLon, Tokyo, TKY2JGD};
+
+/// 日本列島を斜めに横切る点
+fn points() -> Vec<LatLon> {
+    (0..1000)
+        .map(|i| f64::from(i) / 1000.)
+        .map(|t| jgd::from_tokyo(31. + 12. * t, 130. + 12. * t).degrees())
+        .collect()
+}
+
+/// 索引を持たない、組み込みと同じグリッド
+fn unindexed() -> GridBuf {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/par/TKY2JGD.in");
+    GridBuf::open(path).unwrap()
+}
+
+fn bilinear(c: &mut Criterion) {
+    let points = points();
+    let unindexed = unindexed();
+    let unindexed = unindexed.as_grid();
+    let indexed = TKY2JGD.indexed();
+
+    let mut group = c.benchmark_group("bilinear");
+    group.bench_function("Grid (binary search)", |b| {
+        b.iter(|| {
+            for p in &points {
+                black_box(unindexed.bilinear(black_box(*p)));
+            }
+        })
+    });
+    group.bench_function("TKY2JGD (lazy index)", |b| {
+        b.iter(|| {
+            for p in &points {
+                black_box(TKY2JGD.bilinear(black_box(*p)));
+            }
+        })
+    });
+    group.bench_function("IndexedGrid", |b| {
+        b.iter(|| {
+            for p in &points {
+                black_box(indexed.bilinear(black_box(*p)));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn to_jgd2000(c: &mut Criterion) {
+    let points = points();
+    let tokyo = points.into_iter().map(Tokyo::new).collect::<Vec<_>>();
+    c.bench_function("Tokyo::to_jgd2000", |b| {
+        b.iter(|| {
+            for p in &tokyo {
+                black_box(black_box(p).to_jgd2000());
+            }
+        })
+    });
+}
+
+fn indexed(c: &mut Criterion) {
+    c.bench_function("Grid::indexed", |b| b.iter(|| TKY2JGD.indexed()));
+}
+
+criterion_group!(benches, bilinear, to_jgd2000, indexed);
+criterion_main!(benches);
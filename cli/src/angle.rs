@@ -0,0 +1,80 @@
+/// 度分秒の角度を度に変換する。
+///
+/// 次の表記を受け付ける。
+///
+/// - `35°39′29.1572″`, `35°39'29.1572"`, `35d39m29.1572s`, `35:39:29.1572`
+/// - `353929.1572` (TKY2JGD や PatchJGD と同じ、度分秒を詰めた表記)
+///
+/// 先頭の `-` または末尾の `S`, `W` は負の角度を表す。末尾の `N`, `E` は無視する。
+pub fn parse_dms(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let (s, negative) = if let Some(s) = s.strip_prefix('-') {
+        (s, true)
+    } else if let Some(s) = s.strip_suffix(['S', 'W']) {
+        (s, true)
+    } else {
+        (s.strip_suffix(['N', 'E']).unwrap_or(s), false)
+    };
+
+    let parts = s
+        .split(['°', '′', '″', '\'', '"', 'd', 'm', 's', ':'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>();
+    let (d, m, sec) = match parts[..] {
+        [packed] => unpack(packed)?,
+        [d, m, sec] => (d.parse().ok()?, m.parse().ok()?, sec.parse().ok()?),
+        [d, m] => (d.parse().ok()?, m.parse().ok()?, 0.),
+        _ => return None,
+    };
+    if !(0..60).contains(&m) || !(0. ..60.).contains(&sec) {
+        return None;
+    }
+
+    let degrees = f64::from(d) + f64::from(m) / 60. + sec / 3_600.;
+    Some(if negative { -degrees } else { degrees })
+}
+
+/// `dddmmss.sss` を度, 分, 秒に分ける
+fn unpack(packed: &str) -> Option<(i32, i32, f64)> {
+    let (int, frac) = packed.split_once('.').unwrap_or((packed, ""));
+    if int.len() < 5 || !int.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (d, rest) = int.split_at(int.len() - 4);
+    let (m, sec) = rest.split_at(2);
+    let sec = format!("{sec}.{frac}").parse().ok()?;
+    Some((d.parse().ok()?, m.parse().ok()?, sec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dms() {
+        let expected = 35. + 39. / 60. + 29.1572 / 3_600.;
+        for s in [
+            "35°39′29.1572″",
+            "35°39'29.1572\"",
+            "35d39m29.1572s",
+            "35:39:29.1572",
+            "353929.1572",
+            "35°39′29.1572″N",
+        ] {
+            let ret = parse_dms(s).unwrap();
+            assert!((ret - expected).abs() < 1e-12, "{s}: {ret}");
+        }
+        assert_eq!(parse_dms("-353000"), Some(-35.5));
+        assert_eq!(parse_dms("35°30′S"), Some(-35.5));
+        assert_eq!(parse_dms("1393000"), Some(139.5));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(parse_dms("35.5"), None);
+        assert_eq!(parse_dms("356000"), None);
+        assert_eq!(parse_dms("35°39′60″"), None);
+        assert_eq!(parse_dms("abc"), None);
+        assert_eq!(parse_dms(""), None);
+    }
+}
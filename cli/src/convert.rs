@@ -0,0 +1,250 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::PathBuf,
+};
+
+use anyhow::{bail, ensure, Context};
+use clap::{Args, ValueEnum};
+
+use crate::{angle, system::System};
+
+/// 座標を1行に1点ずつ読み、変換して出力する。
+#[derive(Debug, Args)]
+pub struct ConvertArgs {
+    /// Source coordinate system: EPSG code (e.g. 6677) or datum[:projection] (e.g. tokyo, jgd2011:pr9, jgd2000:utm54)
+    #[arg(short, long)]
+    from: System,
+
+    /// Target coordinate system, in the same form as --from
+    #[arg(short, long)]
+    to: System,
+
+    /// Order of the two coordinate columns, for both input and output
+    #[arg(short, long, value_enum, default_value_t = Order::LatLon)]
+    order: Order,
+
+    /// Read geographic input in degrees, minutes, seconds (e.g. 35°39′29.1572″, 35:39:29.1572 or 353929.1572)
+    #[arg(long)]
+    dms: bool,
+
+    /// Column delimiter such as ',' or 'tab' [default: whitespace]
+    #[arg(short, long, value_parser = parse_delimiter)]
+    delimiter: Option<char>,
+
+    /// Number of decimal places [default: 9 for degrees, 4 for meters]
+    #[arg(short, long)]
+    precision: Option<usize>,
+
+    /// Input files. Reads standard input if none or '-'
+    files: Vec<PathBuf>,
+}
+
+/// 座標の列の順序
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Order {
+    /// Latitude then longitude. Northing then easting for projected coordinates
+    LatLon,
+
+    /// Longitude then latitude. Easting then northing for projected coordinates
+    LonLat,
+}
+
+pub fn run(args: ConvertArgs) -> anyhow::Result<()> {
+    ensure!(
+        !args.dms || args.from.is_geographic(),
+        "--dms requires geographic input, but {} is projected",
+        args.from
+    );
+    let converter = Converter::new(&args);
+
+    let mut output = BufWriter::new(io::stdout().lock());
+    let stdin = [PathBuf::from("-")];
+    let files = if args.files.is_empty() {
+        &stdin[..]
+    } else {
+        &args.files[..]
+    };
+    for path in files {
+        let input: Box<dyn BufRead> = if path.as_os_str() == "-" {
+            Box::new(io::stdin().lock())
+        } else {
+            let file =
+                File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+            Box::new(BufReader::new(file))
+        };
+        for (i, line) in input.lines().enumerate() {
+            let line = line.with_context(|| format!("failed to read {}", path.display()))?;
+            let line = converter
+                .convert(&line)
+                .with_context(|| format!("{}:{}", path.display(), i + 1))?;
+            writeln!(output, "{line}")?;
+        }
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// 1行ずつ変換する
+struct Converter {
+    from: System,
+    to: System,
+    north_first: bool,
+    dms: bool,
+    delimiter: Option<char>,
+    precision: usize,
+}
+impl Converter {
+    fn new(args: &ConvertArgs) -> Self {
+        let default_precision = if args.to.is_geographic() { 9 } else { 4 };
+        Self {
+            from: args.from,
+            to: args.to,
+            north_first: args.order == Order::LatLon,
+            dms: args.dms,
+            delimiter: args.delimiter,
+            precision: args.precision.unwrap_or(default_precision),
+        }
+    }
+
+    /// 先頭の2列を変換し、残りの列はそのまま出力する。
+    /// 空行と `#` で始まる行もそのまま出力する。
+    fn convert(&self, line: &str) -> anyhow::Result<String> {
+        if line.trim().is_empty() || line.starts_with('#') {
+            return Ok(line.to_string());
+        }
+        let Some((a, b, rest)) = self.split(line) else {
+            bail!("expected two coordinates: {line}");
+        };
+
+        let coord = self
+            .from
+            .reorder((self.parse(a)?, self.parse(b)?), self.north_first);
+        let coord = self.from.transform(&self.to, coord)?;
+        let (a, b) = self.to.reorder(coord, self.north_first);
+
+        let delimiter = self.delimiter.unwrap_or(' ');
+        let (a, b) = (self.format(a), self.format(b));
+        Ok(format!("{a}{delimiter}{b}{rest}"))
+    }
+
+    /// 先頭の2列と、区切り文字から始まる残りの部分に分ける
+    fn split<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str, &'a str)> {
+        let (a, rest) = self.split_first(line)?;
+        let rest = match self.delimiter {
+            Some(delimiter) => rest.strip_prefix(delimiter)?,
+            None => rest,
+        };
+        let (b, rest) = self.split_first(rest)?;
+        Some((a, b, rest))
+    }
+
+    fn split_first<'a>(&self, s: &'a str) -> Option<(&'a str, &'a str)> {
+        let (field, rest) = match self.delimiter {
+            Some(delimiter) => match s.find(delimiter) {
+                Some(i) => (&s[..i], &s[i..]),
+                None => (s, ""),
+            },
+            None => {
+                let s = s.trim_start();
+                s.split_at(s.find(char::is_whitespace).unwrap_or(s.len()))
+            }
+        };
+        let field = field.trim();
+        (!field.is_empty()).then_some((field, rest))
+    }
+
+    fn parse(&self, field: &str) -> anyhow::Result<f64> {
+        let field = field.trim();
+        let value = if self.dms {
+            angle::parse_dms(field)
+        } else {
+            field.parse().ok()
+        };
+        value.with_context(|| format!("invalid coordinate: {field}"))
+    }
+
+    fn format(&self, value: f64) -> String {
+        format!("{value:.*}", self.precision)
+    }
+}
+
+pub fn parse_delimiter(s: &str) -> Result<char, String> {
+    match s {
+        "tab" | "\\t" => Ok('\t'),
+        "space" => Ok(' '),
+        _ => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(format!("delimiter must be a single character: {s}")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn converter(from: &str, to: &str) -> Converter {
+        Converter {
+            from: from.parse().unwrap(),
+            to: to.parse().unwrap(),
+            north_first: true,
+            dms: false,
+            delimiter: None,
+            precision: 9,
+        }
+    }
+
+    #[test]
+    fn convert() {
+        let c = converter("tokyo", "jgd2000");
+        let (lat, lon) = jgd::from_tokyo(35., 135.).to_jgd2000().degrees().into();
+        let expected = format!("{lat:.9} {lon:.9} name  1");
+        assert_eq!(c.convert("35 135 name  1").unwrap(), expected);
+        assert_eq!(
+            c.convert("  35\t135 name 1").unwrap(),
+            expected.replace("  ", " ")
+        );
+        assert_eq!(c.convert("# comment").unwrap(), "# comment");
+        assert_eq!(c.convert("").unwrap(), "");
+        assert!(c.convert("35").is_err());
+        assert!(c.convert("35 E135").is_err());
+
+        let c = Converter {
+            delimiter: Some(','),
+            ..c
+        };
+        assert!(c.convert("35,135,").unwrap().ends_with(','));
+        assert!(c.convert(",35,135").is_err());
+    }
+
+    #[test]
+    fn options() {
+        let c = Converter {
+            north_first: false,
+            dms: true,
+            delimiter: Some(','),
+            precision: 3,
+            ..converter("jgd2011", "jgd2011:pr9")
+        };
+        let expected = {
+            let (x, y) = jgd::from_jgd2011(35.5, 139.5)
+                .to_plane_rectangular(jgd::Zone::IX)
+                .into();
+            format!("{y:.3},{x:.3}, name")
+        };
+        assert_eq!(c.convert("1393000,353000, name").unwrap(), expected);
+        assert_eq!(c.convert("139°30′,35°30′, name").unwrap(), expected);
+    }
+
+    #[test]
+    fn delimiter() {
+        assert_eq!(parse_delimiter(","), Ok(','));
+        assert_eq!(parse_delimiter("tab"), Ok('\t'));
+        assert!(parse_delimiter(",,").is_err());
+        assert!(parse_delimiter("").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::PathBuf,
+};
+
+use anyhow::{ensure, Context};
+use clap::Args;
+use jgd::{Column, CsvTransform};
+
+use crate::{
+    convert::{parse_delimiter, Order},
+    system::System,
+};
+
+/// CSV の座標の列を変換する。
+#[derive(Debug, Args)]
+pub struct CsvArgs {
+    /// Source coordinate system: EPSG code (e.g. 6677) or datum[:projection] (e.g. tokyo, jgd2011:pr9, jgd2000:utm54)
+    #[arg(short, long)]
+    from: System,
+
+    /// Target coordinate system, in the same form as --from
+    #[arg(short, long)]
+    to: System,
+
+    /// Two coordinate columns in --order, by name in the header (e.g. lat,lon) or by 1-based number with --no-header (e.g. 3,2)
+    #[arg(short, long, value_delimiter = ',', num_args = 1, required = true)]
+    columns: Vec<String>,
+
+    /// Order of the coordinate columns
+    #[arg(short, long, value_enum, default_value_t = Order::LatLon)]
+    order: Order,
+
+    /// Column delimiter such as ';' or 'tab'
+    #[arg(short, long, value_parser = parse_delimiter, default_value = ",")]
+    delimiter: char,
+
+    /// The first row is data, not a header
+    #[arg(long)]
+    no_header: bool,
+
+    /// Append the transformation method and its indicative accuracy in meters to each row
+    #[arg(short, long)]
+    method: bool,
+
+    /// Number of decimal places [default: 9 for degrees, 4 for meters]
+    #[arg(short, long)]
+    precision: Option<usize>,
+
+    /// Input CSV file. Reads standard input if omitted or '-'
+    file: Option<PathBuf>,
+}
+
+pub fn run(args: CsvArgs) -> anyhow::Result<()> {
+    let crs = |system: System| {
+        system
+            .crs()
+            .with_context(|| format!("{system} is not supported for CSV"))
+    };
+    let (from, to) = (crs(args.from)?, crs(args.to)?);
+    let delimiter = u8::try_from(args.delimiter).context("delimiter must be an ASCII character")?;
+    let [a, b] = columns(&args.columns, args.no_header)?;
+    let columns = match args.order {
+        Order::LatLon => [a, b],
+        Order::LonLat => [b, a],
+    };
+
+    let mut transform = CsvTransform::new(from, to, columns)
+        .delimiter(delimiter)
+        .has_header(!args.no_header)
+        .with_method(args.method);
+    if let Some(precision) = args.precision {
+        transform = transform.precision(precision);
+    }
+
+    let output = io::stdout().lock();
+    match &args.file {
+        Some(path) if path.as_os_str() != "-" => {
+            let file =
+                File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+            transform
+                .transform(BufReader::new(file), output)
+                .with_context(|| path.display().to_string())
+        }
+        _ => transform
+            .transform(io::stdin().lock(), output)
+            .context("stdin"),
+    }
+}
+
+/// 列名、またはヘッダー行がなければ1から始まる列番号
+fn columns(columns: &[String], no_header: bool) -> anyhow::Result<[Column; 2]> {
+    let [a, b] = columns else {
+        anyhow::bail!("--columns must be two columns: {}", columns.join(","));
+    };
+    let column = |s: &String| -> anyhow::Result<Column> {
+        if !no_header {
+            return Ok(Column::from(s.as_str()));
+        }
+        let number = s.parse::<usize>().ok().filter(|&n| n > 0);
+        let number = number
+            .with_context(|| format!("column must be a number from 1 with --no-header: {s}"))?;
+        Ok(Column::Index(number - 1))
+    };
+    let columns = [column(a)?, column(b)?];
+    ensure!(
+        columns[0] != columns[1],
+        "coordinate columns must be different"
+    );
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(s: &[&str]) -> Vec<String> {
+        s.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_columns() {
+        let ret = columns(&strings(&["lat", "lon"]), false).unwrap();
+        assert_eq!(ret, [Column::from("lat"), Column::from("lon")]);
+
+        let ret = columns(&strings(&["3", "2"]), true).unwrap();
+        assert_eq!(ret, [Column::Index(2), Column::Index(1)]);
+
+        assert!(columns(&strings(&["lat"]), false).is_err());
+        assert!(columns(&strings(&["lat", "lat"]), false).is_err());
+        assert!(columns(&strings(&["0", "1"]), true).is_err());
+        assert!(columns(&strings(&["lat", "lon"]), true).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+mod angle;
+mod convert;
+mod csv;
+mod system;
+
+use clap::{Parser, Subcommand};
+
+/// 日本の測地系の間で座標を変換する。
+/// Transform coordinates between geodetic datums used in Japan.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Convert coordinates read one point per line, such as `35.0 135.0`.
+    ///
+    /// The first two columns are converted and any following columns are copied as they are.
+    /// Empty lines and lines starting with '#' are also copied.
+    ///
+    /// Example: jgd convert --from tokyo --to jgd2011:pr9 points.txt
+    Convert(convert::ConvertArgs),
+
+    /// Convert coordinate columns of CSV, keeping the other columns as they are.
+    ///
+    /// Only the designated columns are rewritten. Quotes, line endings and the encoding of
+    /// the other columns are preserved byte for byte.
+    ///
+    /// Example: jgd csv --from tokyo --to jgd2011 --columns lat,lon --method points.csv
+    Csv(csv::CsvArgs),
+}
+
+fn main() -> anyhow::Result<()> {
+    match Cli::parse().command {
+        Command::Convert(args) => convert::run(args),
+        Command::Csv(args) => csv::run(args),
+    }
+}
@@ -0,0 +1,170 @@
+use std::{fmt::Display, str::FromStr};
+
+use jgd::{Crs, Datum, Projection, Zone};
+
+/// 座標系。EPSGコードのある座標参照系か、地理座標の Tokyo97。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum System {
+    Crs(Crs),
+    Tokyo97,
+}
+impl System {
+    /// 投影しない地理座標かどうか
+    pub fn is_geographic(&self) -> bool {
+        match self {
+            Self::Crs(crs) => crs.projection() == Projection::Geographic,
+            Self::Tokyo97 => true,
+        }
+    }
+
+    /// EPSGコードのある座標参照系
+    pub fn crs(&self) -> Option<Crs> {
+        match self {
+            Self::Crs(crs) => Some(*crs),
+            Self::Tokyo97 => None,
+        }
+    }
+
+    /// 座標の順序が北向き, 東向きかどうか
+    fn is_north_first(&self) -> bool {
+        match self {
+            Self::Crs(crs) => matches!(
+                crs.projection(),
+                Projection::Geographic | Projection::PlaneRectangular(_)
+            ),
+            Self::Tokyo97 => true,
+        }
+    }
+
+    /// 座標を `to` に変換する。
+    /// 座標の順序は [`Projection`] と同じ。
+    pub fn transform(&self, to: &System, coord: (f64, f64)) -> Result<(f64, f64), jgd::Error> {
+        match (self, to) {
+            (Self::Crs(from), Self::Crs(to)) => from.transform(to, coord),
+            (Self::Tokyo97, Self::Tokyo97) => Ok(coord),
+            // Tokyo97 は JGD2000 とのみ相互に変換できる
+            (Self::Tokyo97, Self::Crs(to)) => {
+                let (lat, lon) = coord;
+                let jgd2000 = jgd::from_tokyo97(lat, lon).to_jgd2000().degrees();
+                jgd2000_geographic().transform(to, jgd2000.into())
+            }
+            (Self::Crs(from), Self::Tokyo97) => {
+                let (lat, lon) = from.transform(&jgd2000_geographic(), coord)?;
+                Ok(jgd::from_jgd2000(lat, lon).to_tokyo97().lat_lon().into())
+            }
+        }
+    }
+
+    /// 北向き, 東向きの順の座標を、この座標系の順序に並べ替える。逆も同じ。
+    pub fn reorder(&self, coord: (f64, f64), north_first: bool) -> (f64, f64) {
+        if self.is_north_first() == north_first {
+            coord
+        } else {
+            (coord.1, coord.0)
+        }
+    }
+}
+impl FromStr for System {
+    type Err = String;
+
+    /// `EPSG:6677` や `6677` のようなEPSGコード、
+    /// または `jgd2011` や `jgd2011:pr9`, `jgd2000:utm54`, `jgd2011:webmercator` のような測地系と投影法。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        let code = lower.strip_prefix("epsg:").unwrap_or(&lower);
+        if let Ok(code) = code.parse() {
+            return Crs::from_epsg(code)
+                .map(Self::Crs)
+                .ok_or_else(|| format!("unsupported EPSG code: {code}"));
+        }
+
+        let (datum, projection) = lower.split_once(':').unwrap_or((&lower, "geographic"));
+        let datum = match datum {
+            "tokyo" => Datum::Tokyo,
+            "tokyo97" => Datum::Tokyo97,
+            "jgd2000" => Datum::Jgd2000,
+            "jgd2011" => Datum::Jgd2011,
+            _ => return Err(format!("unknown datum: {datum}")),
+        };
+        let projection = parse_projection(projection)
+            .ok_or_else(|| format!("unknown projection: {projection}"))?;
+        if datum == Datum::Tokyo97 {
+            return match projection {
+                Projection::Geographic => Ok(Self::Tokyo97),
+                _ => Err("tokyo97 supports geographic coordinates only".to_string()),
+            };
+        }
+        Crs::new(datum, projection)
+            .map(Self::Crs)
+            .ok_or_else(|| format!("unsupported combination: {s}"))
+    }
+}
+impl Display for System {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Crs(crs) => crs.fmt(f),
+            Self::Tokyo97 => f.write_str("tokyo97"),
+        }
+    }
+}
+
+fn parse_projection(s: &str) -> Option<Projection> {
+    if let Some(zone) = s.strip_prefix("pr") {
+        return Zone::new(zone.parse().ok()?).map(Projection::PlaneRectangular);
+    }
+    if let Some(zone) = s.strip_prefix("utm") {
+        return Some(Projection::Utm(zone.parse().ok()?));
+    }
+    match s {
+        "geographic" => Some(Projection::Geographic),
+        "webmercator" => Some(Projection::WebMercator),
+        _ => None,
+    }
+}
+
+fn jgd2000_geographic() -> Crs {
+    Crs::new(Datum::Jgd2000, Projection::Geographic).expect("EPSG:4612")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> System {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(parse("EPSG:6677"), parse("jgd2011:pr9"));
+        assert_eq!(parse("6677"), parse("JGD2011:PR9"));
+        assert_eq!(parse("4301"), parse("tokyo"));
+        assert_eq!(parse("3099"), parse("jgd2000:utm53"));
+        assert_eq!(parse("3857"), parse("jgd2011:webmercator"));
+        assert_eq!(parse("tokyo97"), System::Tokyo97);
+        assert!("4326".parse::<System>().is_err());
+        assert!("tokyo97:pr9".parse::<System>().is_err());
+        assert!("jgd2011:pr20".parse::<System>().is_err());
+        assert!("wgs84".parse::<System>().is_err());
+    }
+
+    #[test]
+    fn tokyo97() {
+        let (lat, lon) = (35., 135.);
+        let expected = jgd::from_tokyo97(lat, lon).to_jgd2000().degrees().into();
+        let ret = System::Tokyo97.transform(&parse("jgd2000"), (lat, lon));
+        assert_eq!(ret, Ok(expected));
+
+        let expected = jgd::from_jgd2000(lat, lon).to_tokyo97().lat_lon().into();
+        let ret = parse("jgd2000").transform(&System::Tokyo97, (lat, lon));
+        assert_eq!(ret, Ok(expected));
+    }
+
+    #[test]
+    fn reorder() {
+        assert_eq!(parse("jgd2011").reorder((1., 2.), false), (2., 1.));
+        assert_eq!(parse("jgd2011:pr9").reorder((1., 2.), true), (1., 2.));
+        assert_eq!(parse("jgd2011:utm54").reorder((1., 2.), true), (2., 1.));
+        assert_eq!(parse("3857").reorder((1., 2.), false), (1., 2.));
+    }
+}
@@ -0,0 +1,216 @@
+#[cfg(all(feature = "rayon", any(feature = "tky2jgd", feature = "patchjgd")))]
+use rayon::{iter::ParallelIterator, slice::ParallelSliceMut};
+
+use crate::LatLon;
+
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+use crate::grid::Cache;
+
+#[cfg(feature = "tky2jgd")]
+use crate::{Jgd2000, Tokyo, TKY2JGD};
+
+#[cfg(feature = "patchjgd")]
+use crate::TOUHOKUTAIHEIYOUOKI2011;
+
+/// 一括変換できる座標。
+/// Coordinate which can be transformed in batch.
+///
+/// [`LatLon`] と、度単位の緯度と経度のペア `(f64, f64)` に実装されている。
+pub trait Degrees: Copy {
+    #[doc(hidden)]
+    fn to_lat_lon(self) -> LatLon;
+    #[doc(hidden)]
+    fn from_lat_lon(p: LatLon) -> Self;
+}
+impl Degrees for LatLon {
+    fn to_lat_lon(self) -> LatLon {
+        self
+    }
+    fn from_lat_lon(p: LatLon) -> Self {
+        p
+    }
+}
+impl Degrees for (f64, f64) {
+    fn to_lat_lon(self) -> LatLon {
+        LatLon::new(self.0, self.1)
+    }
+    fn from_lat_lon(p: LatLon) -> Self {
+        p.into()
+    }
+}
+
+/// [`Tokyo::to_jgd2000`] と同じ変換を、すべての座標に適用する。
+/// Transform all points from Tokyo Datum to JGD2000 in place.
+///
+/// 直前の座標と同じ3次メッシュに属する座標は、パラメータグリッドの探索を省略する。
+/// 近い座標が連続するように並んでいると速い。
+///
+/// # Examples
+///
+/// ```
+/// let mut points = [(35.0, 135.0), (35.0001, 135.0001)];
+/// jgd::tokyo_to_jgd2000(&mut points);
+/// ```
+#[cfg(feature = "tky2jgd")]
+pub fn tokyo_to_jgd2000<P: Degrees>(points: &mut [P]) {
+    let mut cache = Cache::default();
+    map(points, |p| match TKY2JGD.bilinear_cached(p, &mut cache) {
+        Some(shift) => p + shift,
+        None => Tokyo::new(p).to_tokyo97().to_jgd2000().degrees(),
+    })
+}
+
+/// [`Jgd2000::to_tokyo`] と同じ変換を、すべての座標に適用する。
+/// Transform all points from JGD2000 to Tokyo Datum in place.
+///
+/// See [`tokyo_to_jgd2000`].
+#[cfg(feature = "tky2jgd")]
+pub fn jgd2000_to_tokyo<P: Degrees>(points: &mut [P]) {
+    let mut cache = Cache::default();
+    map(points, |p| {
+        match TKY2JGD.bilinear_inverse_cached(p, &mut cache) {
+            Some(shift) => p - shift,
+            None => Jgd2000::new(p).to_tokyo97().to_tokyo().degrees(),
+        }
+    })
+}
+
+/// [`Jgd2000::to_jgd2011`](crate::Jgd2000::to_jgd2011) と同じ変換を、すべての座標に適用する。
+/// Transform all points from JGD2000 to JGD2011 in place.
+///
+/// See [`tokyo_to_jgd2000`].
+#[cfg(feature = "patchjgd")]
+pub fn jgd2000_to_jgd2011<P: Degrees>(points: &mut [P]) {
+    let mut cache = Cache::default();
+    map(points, |p| {
+        p + TOUHOKUTAIHEIYOUOKI2011
+            .bilinear_cached(p, &mut cache)
+            .unwrap_or_default()
+    })
+}
+
+/// [`Jgd2011::to_jgd2000`](crate::Jgd2011::to_jgd2000) と同じ変換を、すべての座標に適用する。
+/// Transform all points from JGD2011 to JGD2000 in place.
+///
+/// See [`tokyo_to_jgd2000`].
+#[cfg(feature = "patchjgd")]
+pub fn jgd2011_to_jgd2000<P: Degrees>(points: &mut [P]) {
+    let mut cache = Cache::default();
+    map(points, |p| {
+        p - TOUHOKUTAIHEIYOUOKI2011
+            .bilinear_inverse_cached(p, &mut cache)
+            .unwrap_or_default()
+    })
+}
+
+/// [`tokyo_to_jgd2000`] を並列に実行する。
+/// Parallel version of [`tokyo_to_jgd2000`].
+#[cfg(all(feature = "rayon", feature = "tky2jgd"))]
+pub fn par_tokyo_to_jgd2000<P: Degrees + Send>(points: &mut [P]) {
+    par_chunks(points, tokyo_to_jgd2000)
+}
+
+/// [`jgd2000_to_tokyo`] を並列に実行する。
+/// Parallel version of [`jgd2000_to_tokyo`].
+#[cfg(all(feature = "rayon", feature = "tky2jgd"))]
+pub fn par_jgd2000_to_tokyo<P: Degrees + Send>(points: &mut [P]) {
+    par_chunks(points, jgd2000_to_tokyo)
+}
+
+/// [`jgd2000_to_jgd2011`] を並列に実行する。
+/// Parallel version of [`jgd2000_to_jgd2011`].
+#[cfg(all(feature = "rayon", feature = "patchjgd"))]
+pub fn par_jgd2000_to_jgd2011<P: Degrees + Send>(points: &mut [P]) {
+    par_chunks(points, jgd2000_to_jgd2011)
+}
+
+/// [`jgd2011_to_jgd2000`] を並列に実行する。
+/// Parallel version of [`jgd2011_to_jgd2000`].
+#[cfg(all(feature = "rayon", feature = "patchjgd"))]
+pub fn par_jgd2011_to_jgd2000<P: Degrees + Send>(points: &mut [P]) {
+    par_chunks(points, jgd2011_to_jgd2000)
+}
+
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+fn map<P: Degrees>(points: &mut [P], mut f: impl FnMut(LatLon) -> LatLon) {
+    for p in points {
+        *p = P::from_lat_lon(f(p.to_lat_lon()));
+    }
+}
+
+/// 並列に変換する単位。連続した座標の探索の省略が効くよう、ある程度大きくする。
+#[cfg(all(feature = "rayon", any(feature = "tky2jgd", feature = "patchjgd")))]
+const CHUNK: usize = 4096;
+
+#[cfg(all(feature = "rayon", any(feature = "tky2jgd", feature = "patchjgd")))]
+fn par_chunks<P: Degrees + Send>(points: &mut [P], f: fn(&mut [P])) {
+    points.par_chunks_mut(CHUNK).for_each(f)
+}
+
+#[cfg(all(test, any(feature = "tky2jgd", feature = "patchjgd")))]
+mod tests {
+    use super::*;
+
+    /// 3次メッシュ内、メッシュ境界、パラメータグリッドの範囲外を含む
+    fn points() -> Vec<(f64, f64)> {
+        let mut points = Vec::new();
+        for i in 0..50 {
+            let d = f64::from(i) * 0.001;
+            points.push((35.0 + d, 135.0 + d));
+            points.push((38.26 + d, 140.87 - d));
+        }
+        points.push((36.31, 143.0));
+        points.push((35.0, 135.0));
+        points
+    }
+
+    #[cfg(feature = "tky2jgd")]
+    #[test]
+    fn same_as_point() {
+        let mut sut = points();
+        tokyo_to_jgd2000(&mut sut);
+        for (ret, (lat, lon)) in sut.iter().zip(points()) {
+            let exp: (f64, f64) = crate::from_tokyo(lat, lon).to_jgd2000().into();
+            assert_eq!(*ret, exp);
+        }
+
+        let mut sut = points();
+        jgd2000_to_tokyo(&mut sut);
+        for (ret, (lat, lon)) in sut.iter().zip(points()) {
+            let exp: (f64, f64) = crate::from_jgd2000(lat, lon).to_tokyo().into();
+            assert_eq!(*ret, exp);
+        }
+    }
+
+    #[cfg(feature = "patchjgd")]
+    #[test]
+    fn same_as_point_patchjgd() {
+        let points = points()
+            .into_iter()
+            .map(|(lat, lon)| LatLon::new(lat, lon))
+            .collect::<Vec<_>>();
+
+        let mut sut = points.clone();
+        jgd2000_to_jgd2011(&mut sut);
+        for (ret, p) in sut.iter().zip(&points) {
+            assert_eq!(*ret, crate::Jgd2000::new(*p).to_jgd2011().degrees());
+        }
+
+        let mut sut = points.clone();
+        jgd2011_to_jgd2000(&mut sut);
+        for (ret, p) in sut.iter().zip(&points) {
+            assert_eq!(*ret, crate::Jgd2011::new(*p).to_jgd2000().degrees());
+        }
+    }
+
+    #[cfg(all(feature = "rayon", feature = "tky2jgd"))]
+    #[test]
+    fn parallel() {
+        let points = points().repeat(100);
+        let mut sut = points.clone();
+        par_tokyo_to_jgd2000(&mut sut);
+        let mut exp = points;
+        tokyo_to_jgd2000(&mut exp);
+        assert_eq!(sut, exp);
+    }
+}
@@ -101,6 +101,53 @@ impl From<LatLon> for (f64, f64) {
     }
 }
 
+/// 緯度経度と楕円体高。
+/// Latitude, longitude and ellipsoidal height of a coordinate.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct LatLonHeight {
+    lat_lon: LatLon,
+    height: f64,
+}
+impl LatLonHeight {
+    /// 緯度経度と楕円体高から変換する。
+    /// Converts from latitude, longitude and ellipsoidal height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::{LatLon, LatLonHeight};
+    ///
+    /// let fuji = LatLonHeight::new(LatLon::from_dms((35, 21, 39.0), (138, 43, 39.0)), 3776.0);
+    /// ```
+    pub fn new(lat_lon: LatLon, height: f64) -> Self {
+        Self { lat_lon, height }
+    }
+
+    /// 緯度経度。
+    /// Latitude and longitude.
+    pub fn lat_lon(&self) -> LatLon {
+        self.lat_lon
+    }
+
+    /// 楕円体高 (メートル)。
+    /// Ellipsoidal height in meters.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+}
+impl From<LatLon> for LatLonHeight {
+    /// 楕円体高を0とする。
+    /// Ellipsoidal height is zero.
+    fn from(lat_lon: LatLon) -> Self {
+        Self::new(lat_lon, 0.)
+    }
+}
+impl From<LatLonHeight> for (f64, f64, f64) {
+    fn from(p: LatLonHeight) -> Self {
+        (p.lat_lon.lat, p.lat_lon.lon, p.height)
+    }
+}
+
 /// 度分秒。
 /// Degrees, minutes, seconds.
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -147,6 +194,7 @@ impl<D: Into<i32>, M: Into<i32>, S: Into<f64>> From<(D, M, S)> for Dms {
 
 /// 三次元直交座標。
 /// Earth-centered, Earth-fixed coordinate.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, Copy)]
 pub struct ECEF {
     x: f64,
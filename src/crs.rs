@@ -1,14 +1,19 @@
 use crate::{
     coord::ECEF,
-    earth::{BESSEL, GRS80},
-    LatLon,
+    earth::{Ellipsoid, BESSEL, GRS80},
+    error::check,
+    patch, Date, Error, LatLon, LatLonHeight, Patch, PlaneRectangular, SemiDyna, Utm, WebMercator,
+    Zone, PATCHES,
 };
 
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+use crate::{Interpolation, Method};
+
 #[cfg(feature = "tky2jgd")]
-use crate::TKY2JGD;
+use crate::{island, TKY2JGD};
 
 #[cfg(feature = "patchjgd")]
-use crate::TOUHOKUTAIHEIYOUOKI2011;
+use crate::{Grid, MicroMeter, TOUHOKUTAIHEIYOUOKI2011};
 
 /// [`Tokyo`] から変換する。
 /// Transform from a coordinate in Tokyo Datum.
@@ -16,12 +21,26 @@ use crate::TOUHOKUTAIHEIYOUOKI2011;
 /// # Examples
 ///
 /// ```
+/// #[cfg(feature = "tky2jgd")]
 /// let (lat, lon) = jgd::from_tokyo(35.0, 135.0).to_jgd2000().into();
 /// ```
 pub fn from_tokyo(lat: f64, lon: f64) -> Tokyo {
     Tokyo::new(LatLon::new(lat, lon))
 }
 
+/// 楕円体高とともに [`Tokyo`] から変換する。
+/// Transform from a coordinate in Tokyo Datum with ellipsoidal height.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "tky2jgd")]
+/// let (lat, lon, height) = jgd::from_tokyo_3d(35.0, 135.0, 100.0).to_jgd2000_3d().into();
+/// ```
+pub fn from_tokyo_3d(lat: f64, lon: f64, height: f64) -> Tokyo {
+    Tokyo::new_3d(LatLonHeight::new(LatLon::new(lat, lon), height))
+}
+
 /// [`Tokyo97`] から変換する。
 /// Transform from a coordinate in Tokyo97.
 ///
@@ -34,29 +53,103 @@ pub fn from_tokyo97(lat: f64, lon: f64) -> Tokyo97 {
     Tokyo97::new(LatLon::new(lat, lon))
 }
 
+/// 楕円体高とともに [`Tokyo97`] から変換する。
+/// Transform from a coordinate in Tokyo97 with ellipsoidal height.
+///
+/// # Examples
+///
+/// ```
+/// let (lat, lon, height) = jgd::from_tokyo97_3d(35.0, 135.0, 100.0).to_jgd2000_3d().into();
+/// ```
+pub fn from_tokyo97_3d(lat: f64, lon: f64, height: f64) -> Tokyo97 {
+    Tokyo97::new_3d(LatLonHeight::new(LatLon::new(lat, lon), height))
+}
+
 /// [`Jgd2000`] から変換する。
 /// Transform from a coordinate in JGD2000.
 ///
 /// # Examples
 ///
 /// ```
+/// #[cfg(feature = "patchjgd")]
 /// let (lat, lon) = jgd::from_jgd2000(35.0, 135.0).to_jgd2011().into();
 /// ```
 pub fn from_jgd2000(lat: f64, lon: f64) -> Jgd2000 {
     Jgd2000::new(LatLon::new(lat, lon))
 }
 
+/// 楕円体高とともに [`Jgd2000`] から変換する。
+/// Transform from a coordinate in JGD2000 with ellipsoidal height.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "patchjgd")]
+/// let (lat, lon, height) = jgd::from_jgd2000_3d(35.0, 135.0, 100.0).to_jgd2011_3d().into();
+/// ```
+pub fn from_jgd2000_3d(lat: f64, lon: f64, height: f64) -> Jgd2000 {
+    Jgd2000::new_3d(LatLonHeight::new(LatLon::new(lat, lon), height))
+}
+
 /// [`Jgd2011`] から変換する。
 /// Transform from a coordinate in JGD2011.
-fn _from_jgd2011(lat: f64, lon: f64) -> Jgd2011 {
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "patchjgd")]
+/// let (lat, lon) = jgd::from_jgd2011(35.0, 135.0).to_jgd2000().into();
+/// ```
+pub fn from_jgd2011(lat: f64, lon: f64) -> Jgd2011 {
     Jgd2011::new(LatLon::new(lat, lon))
 }
 
+/// 楕円体高とともに [`Jgd2011`] から変換する。
+/// Transform from a coordinate in JGD2011 with ellipsoidal height.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "patchjgd")]
+/// let (lat, lon, height) = jgd::from_jgd2011_3d(35.0, 135.0, 100.0).to_jgd2000_3d().into();
+/// ```
+pub fn from_jgd2011_3d(lat: f64, lon: f64, height: f64) -> Jgd2011 {
+    Jgd2011::new_3d(LatLonHeight::new(LatLon::new(lat, lon), height))
+}
+
+/// 投影座標系の基準となる地理座標系。
+/// Geographic coordinate reference system on which projected ones are based.
+///
+/// [`Tokyo`], [`Jgd2000`], [`Jgd2011`] に実装されている。
+pub trait Geographic {
+    #[doc(hidden)]
+    const ELLIPSOID: Ellipsoid;
+
+    #[doc(hidden)]
+    const EPSG: u32;
+
+    #[doc(hidden)]
+    const PLANE_RECTANGULAR_EPSG: u32;
+
+    #[doc(hidden)]
+    const UTM_EPSG: Option<u32>;
+
+    /// 緯度経度から変換する。
+    /// Transform from latitude and longitude.
+    fn new(degrees: LatLon) -> Self;
+
+    /// 緯度経度。
+    /// Latitude and longitude.
+    fn degrees(&self) -> LatLon;
+}
+
 /// 旧日本測地系。Tokyo Datum, The older Japanese Datum.
 ///
 /// EPSG: 4301
+#[derive(Debug)]
 pub struct Tokyo {
     degrees: LatLon,
+    height: f64,
 }
 impl Tokyo {
     /// [`Tokyo`] から変換する。
@@ -68,6 +161,7 @@ impl Tokyo {
     /// use jgd::{LatLon, Tokyo};
     ///
     /// let tokyo = LatLon::from_dms((35, 0, 0.0), (135, 0, 0.0));
+    /// #[cfg(feature = "tky2jgd")]
     /// let jgd2000 = Tokyo::new(tokyo).to_jgd2000().degrees();
     /// ```
     ///
@@ -75,7 +169,19 @@ impl Tokyo {
     ///
     /// すでに度単位の座標が分かっている場合は、代わりに [`from_tokyo`] を使える。
     pub fn new(degrees: LatLon) -> Self {
-        Self { degrees }
+        Self {
+            degrees,
+            height: 0.,
+        }
+    }
+
+    /// 楕円体高とともに変換する。
+    /// Transform from a coordinate with ellipsoidal height.
+    pub fn new_3d(p: LatLonHeight) -> Self {
+        Self {
+            degrees: p.lat_lon(),
+            height: p.height(),
+        }
     }
 
     /// [`TKY2JGD`] を用いて [`Jgd2000`] へ変換する。
@@ -87,21 +193,157 @@ impl Tokyo {
     ///
     /// パラメータグリッドの範囲外の座標は、[`Tokyo97`] を経由し、一律の数式による変換にフォールバックされる。
     /// 複数の座標で表される形状が、パラメータグリッドの範囲内外をまたがっていると、変換後の形状が大きく変わる可能性がある。
+    /// 形状全体を一貫した方法で変換するには、[`Shape::from_tokyo`](crate::Shape::from_tokyo) を用いる。
     ///
     /// 国土地理院によるオリジナルの実装の精度は、一定条件下で「緯度, 経度の標準偏差はそれぞれ9cm, 8cm」[(飛田, 2001)](crate#references) とされている。
     #[cfg(feature = "tky2jgd")]
     pub fn to_jgd2000(&self) -> Jgd2000 {
+        self.to_jgd2000_with_method(Interpolation::Bilinear).0
+    }
+
+    /// [`TKY2JGD`] の四隅のうち、存在するパラメータのみを用いて [`Jgd2000`] へ変換する。
+    /// Transform to JGD2000, interpolating from the existing corners.
+    ///
+    /// [`Grid::bilinear_partial`](crate::Grid::bilinear_partial) を用いる。
+    /// 沿岸の埋立地や小島など、四隅の一部しか存在しない座標でも、[`Tokyo::to_jgd2000`] のように一律の数式にフォールバックせず、周囲と連続した変換になる。
+    ///
+    /// 四隅すべてが存在する座標では [`Tokyo::to_jgd2000`] と同じ。
+    /// すべて欠けている座標は、[`Tokyo::to_jgd2000`] と同じくフォールバックされる。
+    #[cfg(feature = "tky2jgd")]
+    pub fn to_jgd2000_partial(&self) -> Jgd2000 {
+        self.to_jgd2000_with_method(Interpolation::Partial).0
+    }
+
+    /// 四隅の一部が欠けている場合は、最近傍の格子点のパラメータを用いて [`Jgd2000`] へ変換する。
+    /// Transform to JGD2000, using the nearest grid point outside the grid.
+    ///
+    /// 四隅すべてが存在する座標では [`Tokyo::to_jgd2000`] と同じ。
+    /// 欠けている場合は、`limit` メートル以内で最も近い格子点のパラメータ [`Grid::nearest`](crate::Grid::nearest) を用いる。
+    /// それも存在しない座標は、[`Tokyo::to_jgd2000`] と同じくフォールバックされる。
+    #[cfg(feature = "tky2jgd")]
+    pub fn to_jgd2000_nearest(&self, limit: f64) -> Jgd2000 {
+        self.to_jgd2000_with_method(Interpolation::Nearest { limit })
+            .0
+    }
+
+    /// 指定された補間方法で [`Jgd2000`] へ変換し、実際に用いられた方法とともに返す。
+    /// Transform to JGD2000, and report the method actually applied.
+    ///
+    /// パラメータグリッドで補間できなかった座標は、[`Tokyo97`] を経由してフォールバックされ、[`Method::ThreeParameter`] となる。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::{Interpolation, Method};
+    ///
+    /// let (jgd2000, method) = jgd::from_tokyo(35.0, 135.0).to_jgd2000_with_method(Interpolation::Bilinear);
+    /// assert_eq!(method, Method::Bilinear);
+    /// assert_eq!(method.accuracy(), Some(0.1));
+    /// ```
+    #[cfg(feature = "tky2jgd")]
+    pub fn to_jgd2000_with_method(&self, interpolation: Interpolation) -> (Jgd2000, Method) {
+        match TKY2JGD.interpolate(self.degrees, interpolation) {
+            Some((shift, method)) => (Jgd2000::new(self.degrees + shift), method),
+            None => (
+                Self::new(self.degrees).to_tokyo97().to_jgd2000(),
+                Method::ThreeParameter,
+            ),
+        }
+    }
+
+    /// 楕円体高とともに [`Jgd2000`] へ変換する。
+    /// Transform to JGD2000 with ellipsoidal height.
+    ///
+    /// 緯度経度は [`Tokyo::to_jgd2000`] と同じく [`TKY2JGD`] を用いて変換し、楕円体高は3パラメータによる変換 [`Tokyo97::to_jgd2000_3d`] の値となる。
+    #[cfg(feature = "tky2jgd")]
+    pub fn to_jgd2000_3d(&self) -> Jgd2000 {
+        let fallback = self.to_tokyo97().to_jgd2000_3d();
         match TKY2JGD.bilinear(self.degrees) {
-            Some(shift) => Jgd2000::new(self.degrees + shift),
-            None => self.to_tokyo97().to_jgd2000(),
+            Some(shift) => {
+                Jgd2000::new_3d(LatLonHeight::new(self.degrees + shift, fallback.height))
+            }
+            None => fallback,
         }
     }
 
-    /// 離島位置の補正量 [(飛田, 2003)](crate#references) を用いて [`Tokyo97`] へ変換する。
-    #[allow(dead_code)]
-    fn to_tokyo97(&self) -> Tokyo97 {
-        // TODO
-        Tokyo97::new(self.degrees)
+    /// [`TKY2JGD`] を用いて [`Jgd2000`] へ変換する。範囲外はエラーとする。
+    /// Fallible version of [`Tokyo::to_jgd2000`].
+    ///
+    /// # Errors
+    ///
+    /// - 座標が不正な場合は [`Error::InvalidCoordinate`]
+    /// - パラメータグリッドの範囲外の場合は、フォールバックせずに [`Error::OutsideGrid`]
+    #[cfg(feature = "tky2jgd")]
+    pub fn try_to_jgd2000(&self) -> Result<Jgd2000, Error> {
+        self.try_to_jgd2000_by(Interpolation::Bilinear)
+    }
+
+    /// [`Tokyo::to_jgd2000_partial`] の、範囲外をエラーとするもの。
+    /// Fallible version of [`Tokyo::to_jgd2000_partial`].
+    ///
+    /// # Errors
+    ///
+    /// [`Tokyo::try_to_jgd2000`] と同じ。
+    #[cfg(feature = "tky2jgd")]
+    pub fn try_to_jgd2000_partial(&self) -> Result<Jgd2000, Error> {
+        self.try_to_jgd2000_by(Interpolation::Partial)
+    }
+
+    /// [`Tokyo::to_jgd2000_nearest`] の、範囲外をエラーとするもの。
+    /// Fallible version of [`Tokyo::to_jgd2000_nearest`].
+    ///
+    /// # Errors
+    ///
+    /// [`Tokyo::try_to_jgd2000`] と同じ。
+    #[cfg(feature = "tky2jgd")]
+    pub fn try_to_jgd2000_nearest(&self, limit: f64) -> Result<Jgd2000, Error> {
+        self.try_to_jgd2000_by(Interpolation::Nearest { limit })
+    }
+
+    /// [`Tokyo::to_jgd2000_3d`] の、範囲外をエラーとするもの。
+    /// Fallible version of [`Tokyo::to_jgd2000_3d`].
+    ///
+    /// # Errors
+    ///
+    /// [`Tokyo::try_to_jgd2000`] と同じ。
+    #[cfg(feature = "tky2jgd")]
+    pub fn try_to_jgd2000_3d(&self) -> Result<Jgd2000, Error> {
+        let degrees = self.try_to_jgd2000()?.degrees;
+        let height = self.to_tokyo97().to_jgd2000_3d().height;
+        Ok(Jgd2000::new_3d(LatLonHeight::new(degrees, height)))
+    }
+
+    #[cfg(feature = "tky2jgd")]
+    fn try_to_jgd2000_by(&self, interpolation: Interpolation) -> Result<Jgd2000, Error> {
+        check(self.lat_lon_height())?;
+        let (shift, _) = TKY2JGD
+            .interpolate(self.degrees, interpolation)
+            .ok_or(Error::OutsideGrid(self.degrees))?;
+        Ok(Jgd2000::new(self.degrees + shift))
+    }
+
+    /// 離島位置の補正量を用いて [`Tokyo97`] へ変換する。
+    ///
+    /// 補正量は、飛田ほか [(2003)](crate#references) の表ではなく、[`TKY2JGD`](crate::TKY2JGD) から求めた近似値。
+    /// 補正量が定められた島以外では、何も行われない。楕円体高は変わらない。
+    #[cfg(feature = "tky2jgd")]
+    pub(crate) fn to_tokyo97(&self) -> Tokyo97 {
+        let degrees = self.degrees + island::shift(self.degrees);
+        Tokyo97::new_3d(LatLonHeight::new(degrees, self.height))
+    }
+
+    /// [`PlaneRectangular`] へ投影する。
+    /// Project to the Japan Plane Rectangular Coordinate System.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Zone;
+    ///
+    /// let (x, y) = jgd::from_tokyo(35.0, 139.0).to_plane_rectangular(Zone::IX).into();
+    /// ```
+    pub fn to_plane_rectangular(&self, zone: Zone) -> PlaneRectangular<Self> {
+        PlaneRectangular::from_degrees(zone, self.degrees)
     }
 
     /// 緯度経度。
@@ -109,6 +351,18 @@ impl Tokyo {
     pub fn degrees(&self) -> LatLon {
         self.degrees
     }
+
+    /// 楕円体高 (メートル)。
+    /// Ellipsoidal height in meters.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// 緯度経度と楕円体高。
+    /// Latitude, longitude and ellipsoidal height.
+    pub fn lat_lon_height(&self) -> LatLonHeight {
+        LatLonHeight::new(self.degrees, self.height)
+    }
 }
 impl From<Tokyo> for (f64, f64) {
     /// 度単位の緯度と経度のペア。
@@ -117,14 +371,37 @@ impl From<Tokyo> for (f64, f64) {
         tokyo.degrees().into()
     }
 }
+impl From<Tokyo> for (f64, f64, f64) {
+    /// 度単位の緯度と経度、メートル単位の楕円体高の組。
+    /// Latitude and longitude in degrees, and ellipsoidal height in meters.
+    fn from(tokyo: Tokyo) -> Self {
+        tokyo.lat_lon_height().into()
+    }
+}
+impl Geographic for Tokyo {
+    const ELLIPSOID: Ellipsoid = BESSEL;
+    const EPSG: u32 = 4301;
+    const PLANE_RECTANGULAR_EPSG: u32 = 30161;
+    const UTM_EPSG: Option<u32> = None;
+
+    fn new(degrees: LatLon) -> Self {
+        Self::new(degrees)
+    }
+
+    fn degrees(&self) -> LatLon {
+        self.degrees
+    }
+}
 
 /// 旧日本測地系。Tokyo 97, The older Japanese Datum.
 ///
 /// 世界測地系を基準に、3パラメータによる変換式で定義された測地系 [(飛田, 1997)](crate#references)。
 ///
 /// 旧日本測地系で測量された座標を世界測地系へ変換するには [`Tokyo`] の方が高精度となる。
+#[derive(Debug)]
 pub struct Tokyo97 {
     degrees: LatLon,
+    height: f64,
 }
 impl Tokyo97 {
     const TO_ITRF94: ECEF = ECEF::new(-146.414, 507.337, 680.507);
@@ -145,23 +422,70 @@ impl Tokyo97 {
     ///
     /// すでに度単位の座標が分かっている場合は、代わりに [`from_tokyo97`] を使える。
     pub fn new(degrees: LatLon) -> Self {
-        Self { degrees }
+        Self {
+            degrees,
+            height: 0.,
+        }
+    }
+
+    /// 楕円体高とともに変換する。
+    /// Transform from a coordinate with ellipsoidal height.
+    pub fn new_3d(p: LatLonHeight) -> Self {
+        Self {
+            degrees: p.lat_lon(),
+            height: p.height(),
+        }
     }
 
     /// 3パラメータ [(飛田, 2001)](crate#references) を用いて [`Jgd2000`] へ変換する。
     /// Transform to JGD2000.
     pub fn to_jgd2000(&self) -> Jgd2000 {
+        Jgd2000::new(Self::new(self.degrees).to_jgd2000_3d().degrees)
+    }
+
+    /// 楕円体高とともに、3パラメータを用いて [`Jgd2000`] へ変換する。
+    /// Transform to JGD2000 with ellipsoidal height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (lat, lon, height) = jgd::from_tokyo97_3d(35.0, 135.0, 100.0).to_jgd2000_3d().into();
+    /// ```
+    pub fn to_jgd2000_3d(&self) -> Jgd2000 {
         // https://www.gsi.go.jp/LAW/G2000-g2000faq-1.htm
         // > 測地成果2000での経度・緯度は、世界測地系であるITRF94座標系とGRS80の楕円体を使用して表します
-        let itrf94 = BESSEL.to_ecef(self.degrees) + Self::TO_ITRF94;
-        Jgd2000::new(GRS80.to_geodetic(itrf94))
+        let itrf94 = BESSEL.to_ecef(self.lat_lon_height()) + Self::TO_ITRF94;
+        Jgd2000::new_3d(GRS80.to_geodetic(itrf94))
     }
 
-    /// 離島位置の補正量 [(飛田, 2003)](crate#references) を用いて [`Tokyo`] へ逆変換する。
+    /// 離島位置の補正量を用いて [`Tokyo`] へ逆変換する。
     /// Inverse of [`Tokyo::to_tokyo97`].
-    fn _to_tokyo(&self) -> Tokyo {
-        // TODO
-        Tokyo::new(self.degrees)
+    #[cfg(feature = "tky2jgd")]
+    pub(crate) fn to_tokyo(&self) -> Tokyo {
+        let degrees = self.degrees + island::shift_inverse(self.degrees);
+        Tokyo::new_3d(LatLonHeight::new(degrees, self.height))
+    }
+
+    /// [`Tokyo97::to_jgd2000`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Tokyo97::to_jgd2000`].
+    ///
+    /// # Errors
+    ///
+    /// 座標が不正な場合は [`Error::InvalidCoordinate`]
+    pub fn try_to_jgd2000(&self) -> Result<Jgd2000, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_jgd2000())
+    }
+
+    /// [`Tokyo97::to_jgd2000_3d`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Tokyo97::to_jgd2000_3d`].
+    ///
+    /// # Errors
+    ///
+    /// 座標が不正な場合は [`Error::InvalidCoordinate`]
+    pub fn try_to_jgd2000_3d(&self) -> Result<Jgd2000, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_jgd2000_3d())
     }
 
     /// 緯度経度。
@@ -169,6 +493,18 @@ impl Tokyo97 {
     pub fn lat_lon(&self) -> LatLon {
         self.degrees
     }
+
+    /// 楕円体高 (メートル)。
+    /// Ellipsoidal height in meters.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// 緯度経度と楕円体高。
+    /// Latitude, longitude and ellipsoidal height.
+    pub fn lat_lon_height(&self) -> LatLonHeight {
+        LatLonHeight::new(self.degrees, self.height)
+    }
 }
 impl From<Tokyo97> for (f64, f64) {
     /// 度単位の緯度と経度のペア。
@@ -177,12 +513,21 @@ impl From<Tokyo97> for (f64, f64) {
         tokyo97.lat_lon().into()
     }
 }
+impl From<Tokyo97> for (f64, f64, f64) {
+    /// 度単位の緯度と経度、メートル単位の楕円体高の組。
+    /// Latitude and longitude in degrees, and ellipsoidal height in meters.
+    fn from(tokyo97: Tokyo97) -> Self {
+        tokyo97.lat_lon_height().into()
+    }
+}
 
 /// 世界測地系。Japanese Geodetic Datum 2000 (JGD2000).
 ///
 /// EPSG: 4612
+#[derive(Debug)]
 pub struct Jgd2000 {
     degrees: LatLon,
+    height: f64,
 }
 impl Jgd2000 {
     /// [`Jgd2000`] から変換する。
@@ -194,6 +539,7 @@ impl Jgd2000 {
     /// use jgd::{LatLon, Jgd2000};
     ///
     /// let jgd2000 = LatLon::from_dms((35, 0, 0.0), (135, 0, 0.0));
+    /// #[cfg(feature = "patchjgd")]
     /// let jgd2011 = Jgd2000::new(jgd2000).to_jgd2011().degrees();
     /// ```
     ///
@@ -201,30 +547,317 @@ impl Jgd2000 {
     ///
     /// すでに度単位の座標が分かっている場合は、代わりに [`from_jgd2000`] を使える。
     pub fn new(degrees: LatLon) -> Self {
-        Self { degrees }
+        Self {
+            degrees,
+            height: 0.,
+        }
+    }
+
+    /// 楕円体高とともに変換する。
+    /// Transform from a coordinate with ellipsoidal height.
+    pub fn new_3d(p: LatLonHeight) -> Self {
+        Self {
+            degrees: p.lat_lon(),
+            height: p.height(),
+        }
     }
 
     /// [`TOUHOKUTAIHEIYOUOKI2011`] を用いて [`Jgd2011`] へ変換する。
     ///
     /// パラメータが存在しない地域では、何も行われない。
     /// 複数の座標で表される形状が、パラメータグリッドの範囲内外をまたがっていると、形状が大きく変わる可能性がある。
+    /// 形状全体を一貫した方法で変換するには、[`Shape::from_jgd2000`](crate::Shape::from_jgd2000) を用いる。
     #[cfg(feature = "patchjgd")]
     pub fn to_jgd2011(&self) -> Jgd2011 {
+        Jgd2011::new(Self::new(self.degrees).to_jgd2011_3d().degrees)
+    }
+
+    /// [`TOUHOKUTAIHEIYOUOKI2011`] の四隅のうち、存在するパラメータのみを用いて [`Jgd2011`] へ変換する。
+    /// Transform to JGD2011, interpolating from the existing corners.
+    ///
+    /// [`Grid::bilinear_partial`](crate::Grid::bilinear_partial) を用いる。
+    /// 四隅すべてが存在する座標では [`Jgd2000::to_jgd2011`] と同じ。すべて欠けている座標では、何も行われない。
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2011_partial(&self) -> Jgd2011 {
+        self.to_jgd2011_with_method(Interpolation::Partial).0
+    }
+
+    /// 四隅の一部が欠けている場合は、最近傍の格子点のパラメータを用いて [`Jgd2011`] へ変換する。
+    /// Transform to JGD2011, using the nearest grid point outside the grid.
+    ///
+    /// 四隅すべてが存在する座標では [`Jgd2000::to_jgd2011`] と同じ。
+    /// 欠けている場合は、`limit` メートル以内で最も近い格子点のパラメータ [`Grid::nearest`](crate::Grid::nearest) を用いる。
+    /// それも存在しない座標では、何も行われない。
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2011_nearest(&self, limit: f64) -> Jgd2011 {
+        self.to_jgd2011_with_method(Interpolation::Nearest { limit })
+            .0
+    }
+
+    /// 指定された補間方法で [`Jgd2011`] へ変換し、実際に用いられた方法とともに返す。
+    /// Transform to JGD2011, and report the method actually applied.
+    ///
+    /// パラメータグリッドで補間できなかった座標では何も行われず、[`Method::Identity`] となる。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::{Interpolation, Method};
+    ///
+    /// let (jgd2011, method) = jgd::from_jgd2000(35.0, 135.0).to_jgd2011_with_method(Interpolation::Bilinear);
+    /// assert_eq!(method, Method::Identity);
+    /// ```
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2011_with_method(&self, interpolation: Interpolation) -> (Jgd2011, Method) {
+        match TOUHOKUTAIHEIYOUOKI2011.interpolate(self.degrees, interpolation) {
+            Some((shift, method)) => (Jgd2011::new(self.degrees + shift), method),
+            None => (Jgd2011::new(self.degrees), Method::Identity),
+        }
+    }
+
+    /// 楕円体高とともに [`Jgd2011`] へ変換する。
+    /// Transform to JGD2011 with ellipsoidal height.
+    ///
+    /// 緯度経度は [`Jgd2000::to_jgd2011`] と同じ。楕円体高は変わらない。
+    ///
+    /// 標高補正パラメータ (touhokutaiheiyouoki2011_h.par) は組み込まれていない。
+    /// 高さも補正するには、[`Jgd2000::to_jgd2011_3d_with`] を用いる。
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2011_3d(&self) -> Jgd2011 {
         let shift = TOUHOKUTAIHEIYOUOKI2011
             .bilinear(self.degrees)
             .unwrap_or_default();
-        Jgd2011::new(self.degrees + shift)
+        Jgd2011::new_3d(LatLonHeight::new(self.degrees + shift, self.height))
+    }
+
+    /// 任意の標高補正パラメータを用いて、楕円体高も補正して [`Jgd2011`] へ変換する。
+    /// Transform to JGD2011, correcting the height with the given grid.
+    ///
+    /// 緯度経度は [`Jgd2000::to_jgd2011`] と同じ。
+    /// 標高の補正量を、ジオイド高の変化は小さいものとして、楕円体高にそのまま加える。
+    /// 標高補正パラメータが存在しない地域では、楕円体高は変わらない。
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use jgd::{GridBuf, MicroMeter};
+    ///
+    /// let height: GridBuf<MicroMeter> = GridBuf::open("touhokutaiheiyouoki2011_h.par")?;
+    /// let (lat, lon, height) = jgd::from_jgd2000_3d(38.26, 140.87, 100.0)
+    ///     .to_jgd2011_3d_with(&height.as_grid())
+    ///     .into();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2011_3d_with(&self, height: &Grid<MicroMeter>) -> Jgd2011 {
+        let jgd2011 = self.to_jgd2011_3d();
+        let dh = height.bilinear(self.degrees).unwrap_or_default();
+        Jgd2011::new_3d(LatLonHeight::new(jgd2011.degrees, jgd2011.height + dh))
     }
 
     /// [`TKY2JGD`] を用いて [`Tokyo`] へ逆変換する。
     /// Inverse of [`Tokyo::to_jgd2000`].
-    fn _to_tokyo(&self) {}
+    ///
+    /// 順変換で元の座標が再現されるまで、パラメータグリッドの補間を反復する。
+    ///
+    /// # Limitations
+    ///
+    /// パラメータグリッドの範囲外の座標は、[`Tokyo97`] を経由し、一律の数式による変換にフォールバックされる。
+    /// 複数の座標で表される形状が、パラメータグリッドの範囲内外をまたがっていると、変換後の形状が大きく変わる可能性がある。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (lat, lon) = jgd::from_jgd2000(35.0, 135.0).to_tokyo().into();
+    /// ```
+    #[cfg(feature = "tky2jgd")]
+    pub fn to_tokyo(&self) -> Tokyo {
+        self.to_tokyo_with_method().0
+    }
+
+    /// [`Tokyo`] へ逆変換し、実際に用いられた方法とともに返す。
+    #[cfg(feature = "tky2jgd")]
+    pub(crate) fn to_tokyo_with_method(&self) -> (Tokyo, Method) {
+        match TKY2JGD.bilinear_inverse(self.degrees) {
+            Some(shift) => (Tokyo::new(self.degrees - shift), Method::Bilinear),
+            None => (
+                Self::new(self.degrees).to_tokyo97().to_tokyo(),
+                Method::ThreeParameter,
+            ),
+        }
+    }
+
+    /// 楕円体高とともに [`Tokyo`] へ逆変換する。
+    /// Inverse of [`Tokyo::to_jgd2000_3d`].
+    #[cfg(feature = "tky2jgd")]
+    pub fn to_tokyo_3d(&self) -> Tokyo {
+        let fallback = self.to_tokyo97_3d().to_tokyo();
+        match TKY2JGD.bilinear_inverse(self.degrees) {
+            Some(shift) => Tokyo::new_3d(LatLonHeight::new(self.degrees - shift, fallback.height)),
+            None => fallback,
+        }
+    }
 
     /// 3パラメータを用いて [`Tokyo97`] へ逆変換する。
     /// Inverse of [`Tokyo97::to_jgd2000`].
     pub fn to_tokyo97(&self) -> Tokyo97 {
-        let itrf94 = GRS80.to_ecef(self.degrees) - Tokyo97::TO_ITRF94;
-        Tokyo97::new(BESSEL.to_geodetic(itrf94))
+        Tokyo97::new(Self::new(self.degrees).to_tokyo97_3d().degrees)
+    }
+
+    /// 楕円体高とともに、3パラメータを用いて [`Tokyo97`] へ逆変換する。
+    /// Inverse of [`Tokyo97::to_jgd2000_3d`].
+    pub fn to_tokyo97_3d(&self) -> Tokyo97 {
+        let itrf94 = GRS80.to_ecef(self.lat_lon_height()) - Tokyo97::TO_ITRF94;
+        Tokyo97::new_3d(BESSEL.to_geodetic(itrf94))
+    }
+
+    /// [`Jgd2000::to_jgd2011`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2000::to_jgd2011`].
+    ///
+    /// パラメータが存在しない地域は、地震による変動がないものとして、エラーとせずに何も行わない。
+    /// 範囲外かどうかは [`Jgd2000::to_jgd2011_with_method`] で分かる。
+    ///
+    /// # Errors
+    ///
+    /// 座標が不正な場合は [`Error::InvalidCoordinate`]
+    #[cfg(feature = "patchjgd")]
+    pub fn try_to_jgd2011(&self) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_jgd2011())
+    }
+
+    /// [`Jgd2000::to_jgd2011_partial`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2000::to_jgd2011_partial`].
+    ///
+    /// # Errors
+    ///
+    /// [`Jgd2000::try_to_jgd2011`] と同じ。
+    #[cfg(feature = "patchjgd")]
+    pub fn try_to_jgd2011_partial(&self) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_jgd2011_partial())
+    }
+
+    /// [`Jgd2000::to_jgd2011_nearest`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2000::to_jgd2011_nearest`].
+    ///
+    /// # Errors
+    ///
+    /// [`Jgd2000::try_to_jgd2011`] と同じ。
+    #[cfg(feature = "patchjgd")]
+    pub fn try_to_jgd2011_nearest(&self, limit: f64) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_jgd2011_nearest(limit))
+    }
+
+    /// [`Jgd2000::to_jgd2011_3d`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2000::to_jgd2011_3d`].
+    ///
+    /// # Errors
+    ///
+    /// [`Jgd2000::try_to_jgd2011`] と同じ。
+    #[cfg(feature = "patchjgd")]
+    pub fn try_to_jgd2011_3d(&self) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_jgd2011_3d())
+    }
+
+    /// [`TKY2JGD`] を用いて [`Tokyo`] へ逆変換する。範囲外はエラーとする。
+    /// Fallible version of [`Jgd2000::to_tokyo`].
+    ///
+    /// # Errors
+    ///
+    /// - 座標が不正な場合は [`Error::InvalidCoordinate`]
+    /// - パラメータグリッドの範囲外の場合は、フォールバックせずに [`Error::OutsideGrid`]
+    /// - 反復計算が収束しない場合は [`Error::NotConverged`]
+    #[cfg(feature = "tky2jgd")]
+    pub fn try_to_tokyo(&self) -> Result<Tokyo, Error> {
+        check(self.lat_lon_height())?;
+        let shift = TKY2JGD.try_bilinear_inverse(self.degrees)?;
+        Ok(Tokyo::new(self.degrees - shift))
+    }
+
+    /// [`Jgd2000::to_tokyo_3d`] の、範囲外をエラーとするもの。
+    /// Fallible version of [`Jgd2000::to_tokyo_3d`].
+    ///
+    /// # Errors
+    ///
+    /// [`Jgd2000::try_to_tokyo`] と同じ。
+    #[cfg(feature = "tky2jgd")]
+    pub fn try_to_tokyo_3d(&self) -> Result<Tokyo, Error> {
+        let degrees = self.try_to_tokyo()?.degrees;
+        let height = self.to_tokyo97_3d().to_tokyo().height;
+        Ok(Tokyo::new_3d(LatLonHeight::new(degrees, height)))
+    }
+
+    /// [`Jgd2000::to_tokyo97`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2000::to_tokyo97`].
+    ///
+    /// # Errors
+    ///
+    /// 座標が不正な場合は [`Error::InvalidCoordinate`]
+    pub fn try_to_tokyo97(&self) -> Result<Tokyo97, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_tokyo97())
+    }
+
+    /// [`Jgd2000::to_tokyo97_3d`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2000::to_tokyo97_3d`].
+    ///
+    /// # Errors
+    ///
+    /// 座標が不正な場合は [`Error::InvalidCoordinate`]
+    pub fn try_to_tokyo97_3d(&self) -> Result<Tokyo97, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.to_tokyo97_3d())
+    }
+
+    /// [`PlaneRectangular`] へ投影する。
+    /// Project to the Japan Plane Rectangular Coordinate System.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Zone;
+    ///
+    /// let (x, y) = jgd::from_jgd2000(35.0, 139.0).to_plane_rectangular(Zone::IX).into();
+    /// ```
+    pub fn to_plane_rectangular(&self, zone: Zone) -> PlaneRectangular<Self> {
+        PlaneRectangular::from_degrees(zone, self.degrees)
+    }
+
+    /// [`Utm`] へ投影する。帯は経度から自動的に選ばれる。
+    /// Project to UTM in the zone containing the coordinate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (easting, northing) = jgd::from_jgd2000(35.0, 139.0).to_utm().into();
+    /// ```
+    pub fn to_utm(&self) -> Utm<Self> {
+        Utm::from_degrees(self.degrees)
+    }
+
+    /// 帯を指定して [`Utm`] へ投影する。
+    /// Project to UTM in the specified zone.
+    ///
+    /// # Panics
+    ///
+    /// 帯番号が1から60以外の場合。
+    pub fn to_utm_zone(&self, zone: u8) -> Utm<Self> {
+        Utm::from_degrees_in(zone, self.degrees)
+    }
+
+    /// [`WebMercator`] へ投影する。
+    /// Project to Web Mercator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (x, y) = jgd::from_jgd2000(35.0, 139.0).to_web_mercator().into();
+    /// ```
+    pub fn to_web_mercator(&self) -> WebMercator<Self> {
+        WebMercator::from_degrees(self.degrees)
     }
 
     /// 緯度経度。
@@ -232,6 +865,18 @@ impl Jgd2000 {
     pub fn degrees(&self) -> LatLon {
         self.degrees
     }
+
+    /// 楕円体高 (メートル)。
+    /// Ellipsoidal height in meters.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// 緯度経度と楕円体高。
+    /// Latitude, longitude and ellipsoidal height.
+    pub fn lat_lon_height(&self) -> LatLonHeight {
+        LatLonHeight::new(self.degrees, self.height)
+    }
 }
 impl From<Jgd2000> for (f64, f64) {
     /// 度単位の緯度と経度のペア。
@@ -240,27 +885,368 @@ impl From<Jgd2000> for (f64, f64) {
         jgd2000.degrees().into()
     }
 }
+impl From<Jgd2000> for (f64, f64, f64) {
+    /// 度単位の緯度と経度、メートル単位の楕円体高の組。
+    /// Latitude and longitude in degrees, and ellipsoidal height in meters.
+    fn from(jgd2000: Jgd2000) -> Self {
+        jgd2000.lat_lon_height().into()
+    }
+}
+impl Geographic for Jgd2000 {
+    const ELLIPSOID: Ellipsoid = GRS80;
+    const EPSG: u32 = 4612;
+    const PLANE_RECTANGULAR_EPSG: u32 = 2443;
+    const UTM_EPSG: Option<u32> = Some(3097);
+
+    fn new(degrees: LatLon) -> Self {
+        Self::new(degrees)
+    }
+
+    fn degrees(&self) -> LatLon {
+        self.degrees
+    }
+}
 
 /// 世界測地系。Japanese Geodetic Datum 2011 (JGD2011).
 ///
 /// EPSG: 6668
+#[derive(Debug)]
 pub struct Jgd2011 {
     degrees: LatLon,
+    height: f64,
 }
 impl Jgd2011 {
-    #[allow(dead_code)]
-    fn new(degrees: LatLon) -> Self {
-        Self { degrees }
+    /// [`Jgd2011`] から変換する。
+    /// Transform from a coordinate in JGD2011.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::{LatLon, Jgd2011};
+    ///
+    /// let jgd2011 = LatLon::from_dms((38, 15, 36.0), (140, 52, 12.0));
+    /// #[cfg(feature = "patchjgd")]
+    /// let jgd2000 = Jgd2011::new(jgd2011).to_jgd2000().degrees();
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// すでに度単位の座標が分かっている場合は、代わりに [`from_jgd2011`] を使える。
+    pub fn new(degrees: LatLon) -> Self {
+        Self {
+            degrees,
+            height: 0.,
+        }
+    }
+
+    /// 楕円体高とともに変換する。
+    /// Transform from a coordinate with ellipsoidal height.
+    pub fn new_3d(p: LatLonHeight) -> Self {
+        Self {
+            degrees: p.lat_lon(),
+            height: p.height(),
+        }
     }
 
     /// [`TOUHOKUTAIHEIYOUOKI2011`] を用いて [`Jgd2000`] へ逆変換する。
-    fn _to_jgd2000(&self) {}
+    /// Inverse of [`Jgd2000::to_jgd2011`].
+    ///
+    /// 国土地理院の PatchJGD の逆変換と同様に、順変換で元の座標が再現されるまで、パラメータグリッドの補間を反復する。
+    ///
+    /// パラメータが存在しない地域では、何も行われない。
+    /// 複数の座標で表される形状が、パラメータグリッドの範囲内外をまたがっていると、形状が大きく変わる可能性がある。
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2000(&self) -> Jgd2000 {
+        self.to_jgd2000_with_method().0
+    }
+
+    /// 楕円体高とともに [`Jgd2000`] へ逆変換する。
+    /// Inverse of [`Jgd2000::to_jgd2011_3d`].
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2000_3d(&self) -> Jgd2000 {
+        let shift = TOUHOKUTAIHEIYOUOKI2011
+            .bilinear_inverse(self.degrees)
+            .unwrap_or_default();
+        self.to_jgd2000_3d_by(shift)
+    }
+
+    /// 任意の標高補正パラメータを用いて、楕円体高も補正して [`Jgd2000`] へ逆変換する。
+    /// Inverse of [`Jgd2000::to_jgd2011_3d_with`].
+    ///
+    /// 標高の補正量は、逆変換した [`Jgd2000`] の緯度経度で補間する。
+    #[cfg(feature = "patchjgd")]
+    pub fn to_jgd2000_3d_with(&self, height: &Grid<MicroMeter>) -> Jgd2000 {
+        let jgd2000 = self.to_jgd2000_3d();
+        let dh = height.bilinear(jgd2000.degrees).unwrap_or_default();
+        Jgd2000::new_3d(LatLonHeight::new(jgd2000.degrees, jgd2000.height - dh))
+    }
+
+    /// [`Jgd2000`] へ逆変換し、実際に用いられた方法とともに返す。
+    #[cfg(feature = "patchjgd")]
+    pub(crate) fn to_jgd2000_with_method(&self) -> (Jgd2000, Method) {
+        match TOUHOKUTAIHEIYOUOKI2011.bilinear_inverse(self.degrees) {
+            Some(shift) => (Jgd2000::new(self.degrees - shift), Method::Bilinear),
+            None => (Jgd2000::new(self.degrees), Method::Identity),
+        }
+    }
+
+    /// [`Jgd2011::to_jgd2000`] の、不正な座標と収束しない場合をエラーとするもの。
+    /// Fallible version of [`Jgd2011::to_jgd2000`].
+    ///
+    /// パラメータが存在しない地域は、[`Jgd2000::try_to_jgd2011`] と同じく、エラーとせずに何も行わない。
+    ///
+    /// # Errors
+    ///
+    /// - 座標が不正な場合は [`Error::InvalidCoordinate`]
+    /// - 反復計算が収束しない場合は [`Error::NotConverged`]
+    #[cfg(feature = "patchjgd")]
+    pub fn try_to_jgd2000(&self) -> Result<Jgd2000, Error> {
+        let degrees = Self::new(self.degrees).try_to_jgd2000_3d()?.degrees;
+        Ok(Jgd2000::new(degrees))
+    }
+
+    /// [`Jgd2011::to_jgd2000_3d`] の、不正な座標と収束しない場合をエラーとするもの。
+    /// Fallible version of [`Jgd2011::to_jgd2000_3d`].
+    ///
+    /// # Errors
+    ///
+    /// [`Jgd2011::try_to_jgd2000`] と同じ。
+    #[cfg(feature = "patchjgd")]
+    pub fn try_to_jgd2000_3d(&self) -> Result<Jgd2000, Error> {
+        check(self.lat_lon_height())?;
+        let shift = match TOUHOKUTAIHEIYOUOKI2011.try_bilinear_inverse(self.degrees) {
+            Ok(shift) => shift,
+            Err(Error::OutsideGrid(_)) => LatLon::default(),
+            Err(e) => return Err(e),
+        };
+        Ok(self.to_jgd2000_3d_by(shift))
+    }
+
+    #[cfg(feature = "patchjgd")]
+    fn to_jgd2000_3d_by(&self, shift: LatLon) -> Jgd2000 {
+        Jgd2000::new_3d(LatLonHeight::new(self.degrees - shift, self.height))
+    }
+
+    /// 組み込まれた [`PATCHES`] を用いて、観測日 `epoch` までに発生した地震の座標補正を、発生順に適用する。
+    /// Apply corrections of earthquakes until `epoch` in chronological order.
+    ///
+    /// パラメータが存在しない地域では、その地震の補正は行われない。楕円体高は変わらない。
+    ///
+    /// 現在、[`PATCHES`] には何も組み込まれていないため、座標は変わらない。
+    /// 国土地理院の座標補正パラメータを適用するには、[`Jgd2011::patch_with`] を用いる。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Date;
+    ///
+    /// let jgd2011 = jgd::from_jgd2011(32.8, 130.7);
+    /// let patched = jgd2011.patch(Date::new(2017, 3, 1));
+    /// assert_eq!(patched.degrees(), jgd2011.degrees());
+    /// ```
+    pub fn patch(&self, epoch: Date) -> Jgd2011 {
+        self.patch_with(epoch, PATCHES)
+    }
+
+    /// 組み込まれた [`PATCHES`] を用いて逆変換する。
+    /// Inverse of [`Jgd2011::patch`].
+    pub fn unpatch(&self, epoch: Date) -> Jgd2011 {
+        self.unpatch_with(epoch, PATCHES)
+    }
+
+    /// 任意の座標補正パラメータを用いて、観測日 `epoch` までに発生した地震の座標補正を、発生順に適用する。
+    /// Apply corrections of the given earthquakes until `epoch` in chronological order.
+    pub fn patch_with(&self, epoch: Date, patches: &[Patch]) -> Jgd2011 {
+        let degrees = patch::forward(self.degrees, epoch, patches);
+        Jgd2011::new_3d(LatLonHeight::new(degrees, self.height))
+    }
+
+    /// 任意の座標補正パラメータを用いて逆変換する。
+    /// Inverse of [`Jgd2011::patch_with`].
+    ///
+    /// 新しい地震から順に、補正を反復計算で取り除く。
+    pub fn unpatch_with(&self, epoch: Date, patches: &[Patch]) -> Jgd2011 {
+        let degrees = patch::inverse(self.degrees, epoch, patches);
+        Jgd2011::new_3d(LatLonHeight::new(degrees, self.height))
+    }
+
+    /// セミ・ダイナミック補正により、今期の座標を元期の座標へ変換する。
+    /// Semi-dynamic correction from the current epoch to the reference epoch.
+    ///
+    /// 観測した年度の [`SemiDyna`] を用いる。
+    /// 元期の座標を求めるために、パラメータグリッドの補間を反復する。
+    ///
+    /// パラメータが存在しない地域では、何も行われない。
+    pub fn to_reference_epoch(&self, semidyna: &SemiDyna) -> Jgd2011 {
+        let shift = semidyna
+            .grid()
+            .bilinear_inverse(self.degrees)
+            .unwrap_or_default();
+        Jgd2011::new_3d(LatLonHeight::new(
+            self.degrees - shift.lat_lon(),
+            self.height - shift.height(),
+        ))
+    }
+
+    /// セミ・ダイナミック補正により、元期の座標を今期の座標へ変換する。
+    /// Semi-dynamic correction from the reference epoch to the current epoch.
+    ///
+    /// パラメータが存在しない地域では、何も行われない。
+    pub fn from_reference_epoch(&self, semidyna: &SemiDyna) -> Jgd2011 {
+        let shift = semidyna.grid().bilinear(self.degrees).unwrap_or_default();
+        Jgd2011::new_3d(LatLonHeight::new(
+            self.degrees + shift.lat_lon(),
+            self.height + shift.height(),
+        ))
+    }
+
+    /// [`Jgd2011::patch`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2011::patch`].
+    ///
+    /// # Errors
+    ///
+    /// 座標が不正な場合は [`Error::InvalidCoordinate`]
+    pub fn try_patch(&self, epoch: Date) -> Result<Jgd2011, Error> {
+        self.try_patch_with(epoch, PATCHES)
+    }
+
+    /// [`Jgd2011::unpatch`] の、不正な座標と収束しない場合をエラーとするもの。
+    /// Fallible version of [`Jgd2011::unpatch`].
+    ///
+    /// # Errors
+    ///
+    /// - 座標が不正な場合は [`Error::InvalidCoordinate`]
+    /// - 反復計算が収束しない場合は [`Error::NotConverged`]
+    pub fn try_unpatch(&self, epoch: Date) -> Result<Jgd2011, Error> {
+        self.try_unpatch_with(epoch, PATCHES)
+    }
+
+    /// [`Jgd2011::patch_with`] の、不正な座標をエラーとするもの。
+    /// Fallible version of [`Jgd2011::patch_with`].
+    ///
+    /// パラメータが存在しない地域は、その地震による変動がないものとして、エラーとしない。
+    ///
+    /// # Errors
+    ///
+    /// 座標が不正な場合は [`Error::InvalidCoordinate`]
+    pub fn try_patch_with(&self, epoch: Date, patches: &[Patch]) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        Ok(self.patch_with(epoch, patches))
+    }
+
+    /// [`Jgd2011::unpatch_with`] の、不正な座標と収束しない場合をエラーとするもの。
+    /// Fallible version of [`Jgd2011::unpatch_with`].
+    ///
+    /// # Errors
+    ///
+    /// [`Jgd2011::try_unpatch`] と同じ。
+    pub fn try_unpatch_with(&self, epoch: Date, patches: &[Patch]) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        let degrees = patch::try_inverse(self.degrees, epoch, patches)?;
+        Ok(Jgd2011::new_3d(LatLonHeight::new(degrees, self.height)))
+    }
+
+    /// [`Jgd2011::to_reference_epoch`] の、範囲外をエラーとするもの。
+    /// Fallible version of [`Jgd2011::to_reference_epoch`].
+    ///
+    /// # Errors
+    ///
+    /// - 座標が不正な場合は [`Error::InvalidCoordinate`]
+    /// - パラメータグリッドの範囲外の場合は [`Error::OutsideGrid`]
+    /// - 反復計算が収束しない場合は [`Error::NotConverged`]
+    pub fn try_to_reference_epoch(&self, semidyna: &SemiDyna) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        let shift = semidyna.grid().try_bilinear_inverse(self.degrees)?;
+        Ok(Jgd2011::new_3d(LatLonHeight::new(
+            self.degrees - shift.lat_lon(),
+            self.height - shift.height(),
+        )))
+    }
+
+    /// [`Jgd2011::from_reference_epoch`] の、範囲外をエラーとするもの。
+    /// Fallible version of [`Jgd2011::from_reference_epoch`].
+    ///
+    /// # Errors
+    ///
+    /// - 座標が不正な場合は [`Error::InvalidCoordinate`]
+    /// - パラメータグリッドの範囲外の場合は [`Error::OutsideGrid`]
+    pub fn try_from_reference_epoch(&self, semidyna: &SemiDyna) -> Result<Jgd2011, Error> {
+        check(self.lat_lon_height())?;
+        let shift = semidyna
+            .grid()
+            .bilinear(self.degrees)
+            .ok_or(Error::OutsideGrid(self.degrees))?;
+        Ok(Jgd2011::new_3d(LatLonHeight::new(
+            self.degrees + shift.lat_lon(),
+            self.height + shift.height(),
+        )))
+    }
+
+    /// [`PlaneRectangular`] へ投影する。
+    /// Project to the Japan Plane Rectangular Coordinate System.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Zone;
+    ///
+    /// let (x, y) = jgd::from_jgd2011(35.0, 139.0).to_plane_rectangular(Zone::IX).into();
+    /// ```
+    pub fn to_plane_rectangular(&self, zone: Zone) -> PlaneRectangular<Self> {
+        PlaneRectangular::from_degrees(zone, self.degrees)
+    }
+
+    /// [`Utm`] へ投影する。帯は経度から自動的に選ばれる。
+    /// Project to UTM in the zone containing the coordinate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (easting, northing) = jgd::from_jgd2011(35.0, 139.0).to_utm().into();
+    /// ```
+    pub fn to_utm(&self) -> Utm<Self> {
+        Utm::from_degrees(self.degrees)
+    }
+
+    /// 帯を指定して [`Utm`] へ投影する。
+    /// Project to UTM in the specified zone.
+    ///
+    /// # Panics
+    ///
+    /// 帯番号が1から60以外の場合。
+    pub fn to_utm_zone(&self, zone: u8) -> Utm<Self> {
+        Utm::from_degrees_in(zone, self.degrees)
+    }
+
+    /// [`WebMercator`] へ投影する。
+    /// Project to Web Mercator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (x, y) = jgd::from_jgd2011(35.0, 139.0).to_web_mercator().into();
+    /// ```
+    pub fn to_web_mercator(&self) -> WebMercator<Self> {
+        WebMercator::from_degrees(self.degrees)
+    }
 
     /// 緯度経度。
     /// Latitude and longitude.
     pub fn degrees(&self) -> LatLon {
         self.degrees
     }
+
+    /// 楕円体高 (メートル)。
+    /// Ellipsoidal height in meters.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// 緯度経度と楕円体高。
+    /// Latitude, longitude and ellipsoidal height.
+    pub fn lat_lon_height(&self) -> LatLonHeight {
+        LatLonHeight::new(self.degrees, self.height)
+    }
 }
 impl From<Jgd2011> for (f64, f64) {
     /// 度単位の緯度と経度のペア。
@@ -269,11 +1255,24 @@ impl From<Jgd2011> for (f64, f64) {
         jgd2011.degrees().into()
     }
 }
+impl From<Jgd2011> for (f64, f64, f64) {
+    /// 度単位の緯度と経度、メートル単位の楕円体高の組。
+    /// Latitude and longitude in degrees, and ellipsoidal height in meters.
+    fn from(jgd2011: Jgd2011) -> Self {
+        jgd2011.lat_lon_height().into()
+    }
+}
+impl Geographic for Jgd2011 {
+    const ELLIPSOID: Ellipsoid = GRS80;
+    const EPSG: u32 = 6668;
+    const PLANE_RECTANGULAR_EPSG: u32 = 6669;
+    const UTM_EPSG: Option<u32> = Some(6688);
 
-// /// 平面直角座標系
-// // https://vldb.gsi.go.jp/sokuchi/surveycalc/surveycalc/algorithm/xy2bl/xy2bl.htm
-// // https://sw1227.hatenablog.com/entry/2018/11/30/200702
-// struct _PlaneRectangular<T>(T);
+    fn new(degrees: LatLon) -> Self {
+        Self::new(degrees)
+    }
 
-// /// Webメルカトル座標系
-// struct _WebMercator<T>(T);
+    fn degrees(&self) -> LatLon {
+        self.degrees
+    }
+}
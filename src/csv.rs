@@ -0,0 +1,485 @@
+use std::{
+    borrow::Cow,
+    io::{self, BufRead, Write},
+    ops::Range,
+};
+
+use crate::{Crs, Method, Projection};
+
+/// CSV の列。
+/// Column of CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    /// 0から始まる列番号。
+    /// Zero-based index.
+    Index(usize),
+
+    /// ヘッダー行の列名。
+    /// Name in the header row.
+    Name(String),
+}
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+/// CSV の座標の列を変換する。
+/// Transform coordinate columns of CSV.
+///
+/// 1行ずつ読み込んで書き出すため、大きなファイルも扱える。
+/// 座標の列の値だけを書き換え、その他の列は引用符や空白、改行コード、文字コードを含めてそのまま出力する。
+/// 座標の列が両方とも空の行と、空行もそのまま出力する。
+///
+/// 座標の列は、緯度または北向きの座標, 経度または東向きの座標の順に指定する。
+/// 投影法によらず、変換後の座標も同じ向きの列に書き出す。
+///
+/// [`CsvTransform::with_method`] を指定すると、各行の末尾に、測地系の変換に用いられた方法 `method` と、その精度の目安 `accuracy` (メートル) の列を加える。
+/// 方法は [`Method`] の名前を `bilinear`, `three_parameter` のようにしたもの。精度の目安は [`Method::accuracy`] で、不明な場合は空となる。
+///
+/// # Examples
+///
+/// ```
+/// use jgd::{Crs, CsvTransform};
+///
+/// # #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+/// # {
+/// let input = "id,lat,lon,name\n1,35.0,135.0,\"Kyoto, Japan\"\n";
+/// let tokyo = Crs::from_epsg(4301).unwrap();
+/// let jgd2011 = Crs::from_epsg(6668).unwrap();
+///
+/// let mut output = Vec::new();
+/// CsvTransform::new(tokyo, jgd2011, ["lat", "lon"])
+///     .with_method(true)
+///     .transform(input.as_bytes(), &mut output)
+///     .unwrap();
+///
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.starts_with("id,lat,lon,name,method,accuracy\n1,35.00"));
+/// assert!(output.ends_with(",\"Kyoto, Japan\",bilinear,0.1\n"));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvTransform {
+    from: Crs,
+    to: Crs,
+    columns: [Column; 2],
+    delimiter: u8,
+    has_header: bool,
+    with_method: bool,
+    precision: Option<usize>,
+}
+impl CsvTransform {
+    /// 変換元と変換先の座標参照系、および座標の列から作成する。
+    /// Creates from the source and target CRS, and the coordinate columns.
+    ///
+    /// 区切り文字は `,` で、先頭の行をヘッダー行とする。
+    pub fn new(from: Crs, to: Crs, columns: [impl Into<Column>; 2]) -> Self {
+        Self {
+            from,
+            to,
+            columns: columns.map(Into::into),
+            delimiter: b',',
+            has_header: true,
+            with_method: false,
+            precision: None,
+        }
+    }
+
+    /// 区切り文字を指定する。
+    /// Sets the delimiter.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// 先頭の行をヘッダー行とするかどうかを指定する。
+    /// Sets whether the first row is a header.
+    ///
+    /// ヘッダー行がない場合は、座標の列を [`Column::Index`] で指定する。
+    pub fn has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// 変換方法と精度の目安の列を加えるかどうかを指定する。
+    /// Sets whether to append the method and accuracy columns.
+    pub fn with_method(mut self, with_method: bool) -> Self {
+        self.with_method = with_method;
+        self
+    }
+
+    /// 変換後の座標の小数点以下の桁数を指定する。
+    /// Sets the number of decimal places.
+    ///
+    /// 指定しない場合は、地理座標で9桁 (約0.1ミリメートル)、投影された座標で4桁 (0.1ミリメートル)。
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// `reader` から読み込んだ CSV を変換し、`writer` へ書き出す。
+    /// Reads CSV from `reader`, and writes the transformed one to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// - 読み書きに失敗した場合は、そのエラー
+    /// - 座標の列が見つからない場合は [`io::ErrorKind::InvalidInput`]
+    /// - 座標が数値でない行や、測地系を変換できない場合は [`io::ErrorKind::InvalidData`]
+    pub fn transform(&self, mut reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+        let mut record = Vec::new();
+        let mut line = 0;
+
+        let mut indices = self.columns.clone().map(|column| match column {
+            Column::Index(index) => Ok(index),
+            Column::Name(name) => Err(name),
+        });
+        if self.has_header {
+            if !read_record(&mut reader, &mut record)? {
+                return Ok(());
+            }
+            line += lines(&record);
+            let (body, eol) = split_eol(&record);
+            let names = fields(body, self.delimiter)
+                .into_iter()
+                .map(|range| unquote(strip_bom(&body[range])).into_owned())
+                .collect::<Vec<_>>();
+            for index in &mut indices {
+                if let Err(name) = index {
+                    if let Some(i) = names.iter().position(|n| n == name.as_bytes()) {
+                        *index = Ok(i);
+                    }
+                }
+            }
+
+            writer.write_all(body)?;
+            self.write_method(&mut writer, b"method", b"accuracy")?;
+            writer.write_all(eol)?;
+        }
+        let indices = match indices {
+            [Ok(a), Ok(b)] if a != b => [a, b],
+            [Err(name), _] | [_, Err(name)] => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("column not found: {name}"),
+                ))
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "coordinate columns must be different",
+                ))
+            }
+        };
+
+        while read_record(&mut reader, &mut record)? {
+            let start = line + 1;
+            line += lines(&record);
+            let (body, eol) = split_eol(&record);
+            self.transform_record(body, indices, &mut writer)
+                .map_err(|e| invalid(format!("line {start}: {e}")))?;
+            writer.write_all(eol)?;
+        }
+        writer.flush()
+    }
+
+    /// 改行コードを除いた1レコードを変換する
+    fn transform_record(
+        &self,
+        body: &[u8],
+        [a, b]: [usize; 2],
+        writer: &mut impl Write,
+    ) -> io::Result<()> {
+        if body.is_empty() {
+            return Ok(());
+        }
+        let fields = fields(body, self.delimiter);
+        let (Some(ra), Some(rb)) = (fields.get(a), fields.get(b)) else {
+            return Err(invalid("missing coordinate columns"));
+        };
+        let values = [ra, rb].map(|range| unquote(&body[range.clone()]));
+        let values = values
+            .each_ref()
+            .map(|value| String::from_utf8_lossy(value));
+        let values = values.each_ref().map(|value| value.trim());
+        if values.iter().all(|value| value.is_empty()) {
+            writer.write_all(body)?;
+            return self.write_method(writer, b"", b"");
+        }
+
+        let coord = match values.map(str::parse::<f64>) {
+            [Ok(north), Ok(east)] => reorder(self.from.projection(), (north, east)),
+            _ => {
+                let [a, b] = values;
+                return Err(invalid(format!("invalid coordinate: ({a}, {b})")));
+            }
+        };
+        let (coord, method) = self
+            .from
+            .transform_with_method(&self.to, coord)
+            .map_err(invalid)?;
+        let (north, east) = reorder(self.to.projection(), coord);
+
+        // 座標の列のみを置き換える
+        let mut replacements = [(ra, self.format(north)), (rb, self.format(east))];
+        replacements.sort_by_key(|(range, _)| range.start);
+        let mut pos = 0;
+        for (range, value) in replacements {
+            writer.write_all(&body[pos..range.start])?;
+            writer.write_all(value.as_bytes())?;
+            pos = range.end;
+        }
+        writer.write_all(&body[pos..])?;
+
+        let accuracy = method.accuracy().map(|a| a.to_string()).unwrap_or_default();
+        self.write_method(writer, method_name(method).as_bytes(), accuracy.as_bytes())
+    }
+
+    fn write_method(
+        &self,
+        writer: &mut impl Write,
+        method: &[u8],
+        accuracy: &[u8],
+    ) -> io::Result<()> {
+        if self.with_method {
+            for value in [method, accuracy] {
+                writer.write_all(&[self.delimiter])?;
+                writer.write_all(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn format(&self, value: f64) -> String {
+        let default = match self.to.projection() {
+            Projection::Geographic => 9,
+            _ => 4,
+        };
+        format!("{value:.*}", self.precision.unwrap_or(default))
+    }
+}
+
+/// 北向き, 東向きの順の座標を、投影法の座標の順序に並べ替える。逆も同じ。
+fn reorder(projection: Projection, (a, b): (f64, f64)) -> (f64, f64) {
+    match projection {
+        Projection::Geographic | Projection::PlaneRectangular(_) => (a, b),
+        Projection::Utm(_) | Projection::WebMercator => (b, a),
+    }
+}
+
+fn method_name(method: Method) -> &'static str {
+    match method {
+        Method::Bilinear => "bilinear",
+        Method::Partial => "partial",
+        Method::Nearest => "nearest",
+        Method::ThreeParameter => "three_parameter",
+        Method::Identity => "identity",
+    }
+}
+
+fn invalid<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// 改行コードを含む1レコードを読む。
+///
+/// 引用符の中の改行はレコードの区切りとしない。
+/// 読み終えた場合は `false` を返す。
+fn read_record(reader: &mut impl BufRead, record: &mut Vec<u8>) -> io::Result<bool> {
+    record.clear();
+    loop {
+        if reader.read_until(b'\n', record)? == 0 {
+            return Ok(!record.is_empty());
+        }
+        if record.iter().filter(|&&b| b == b'"').count() % 2 == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// レコードの行数
+fn lines(record: &[u8]) -> usize {
+    record.split_inclusive(|&b| b == b'\n').count()
+}
+
+/// 本体と改行コードに分ける
+fn split_eol(record: &[u8]) -> (&[u8], &[u8]) {
+    let len = record.len();
+    let eol = if record.ends_with(b"\r\n") {
+        2
+    } else if record.ends_with(b"\n") {
+        1
+    } else {
+        0
+    };
+    record.split_at(len - eol)
+}
+
+/// 各列の範囲。区切り文字を含まない
+fn fields(body: &[u8], delimiter: u8) -> Vec<Range<usize>> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, &b) in body.iter().enumerate() {
+        if b == b'"' {
+            quoted = !quoted;
+        } else if b == delimiter && !quoted {
+            fields.push(start..i);
+            start = i + 1;
+        }
+    }
+    fields.push(start..body.len());
+    fields
+}
+
+/// 引用符で囲まれた値を取り出す
+fn unquote(field: &[u8]) -> Cow<'_, [u8]> {
+    match field.trim_ascii() {
+        [b'"', inner @ .., b'"'] => {
+            let mut value = Vec::with_capacity(inner.len());
+            let mut bytes = inner.iter();
+            while let Some(&b) = bytes.next() {
+                value.push(b);
+                if b == b'"' {
+                    // 2つ続く引用符は1つとする
+                    bytes.next();
+                }
+            }
+            Cow::Owned(value)
+        }
+        field => Cow::Borrowed(field),
+    }
+}
+
+/// UTF-8 の BOM を除く
+fn strip_bom(field: &[u8]) -> &[u8] {
+    field.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(transform: &CsvTransform, input: &[u8]) -> io::Result<Vec<u8>> {
+        let mut output = Vec::new();
+        transform.transform(input, &mut output)?;
+        Ok(output)
+    }
+
+    fn jgd2011_to_pr9() -> CsvTransform {
+        let from = Crs::from_epsg(6668).unwrap();
+        let to = Crs::from_epsg(6677).unwrap();
+        CsvTransform::new(from, to, ["lat", "lon"])
+    }
+
+    /// 北緯36度・東経139.8度の第IX系の座標
+    fn pr9(precision: usize) -> (String, String) {
+        let (x, y) = crate::from_jgd2011(36., 139.8)
+            .to_plane_rectangular(crate::Zone::IX)
+            .into();
+        (format!("{x:.precision$}"), format!("{y:.precision$}"))
+    }
+
+    #[test]
+    fn preserve() {
+        // Shift_JIS の「東京」を含む
+        let input = b"\xEF\xBB\xBFid,\"lat\",lon,note\r\n1,36,139.8,\"\x93\x8c\x8b\x9e, \"\"a\"\"\n b\"\r\n\r\n2,\"36.0\", 139.8 ,\r\n3,,,\"\"\r\n";
+        let ret = run(&jgd2011_to_pr9(), input).unwrap();
+
+        let (x, y) = pr9(4);
+        let mut expected = b"\xEF\xBB\xBFid,\"lat\",lon,note\r\n".to_vec();
+        expected.extend(format!("1,{x},{y},").as_bytes());
+        expected.extend(b"\"\x93\x8c\x8b\x9e, \"\"a\"\"\n b\"\r\n\r\n");
+        expected.extend(format!("2,{x},{y},\r\n3,,,\"\"\r\n").as_bytes());
+        assert_eq!(ret, expected);
+    }
+
+    #[test]
+    fn method() {
+        let input = "lon;lat\n139.8;36\n;\n139.8;36";
+        let transform = jgd2011_to_pr9()
+            .delimiter(b';')
+            .with_method(true)
+            .precision(1);
+        let transform = CsvTransform {
+            columns: ["lat", "lon"].map(Column::from),
+            ..transform
+        };
+        let ret = run(&transform, input.as_bytes()).unwrap();
+
+        let (x, y) = pr9(1);
+        let expected =
+            format!("lon;lat;method;accuracy\n{y};{x};identity;\n;;;\n{y};{x};identity;");
+        assert_eq!(String::from_utf8(ret).unwrap(), expected);
+    }
+
+    #[cfg(feature = "tky2jgd")]
+    #[test]
+    fn index() {
+        let tokyo = Crs::from_epsg(4301).unwrap();
+        let jgd2000 = Crs::from_epsg(4612).unwrap();
+        let transform = CsvTransform::new(tokyo, jgd2000, [2, 1])
+            .has_header(false)
+            .with_method(true);
+        let ret = run(&transform, b"a,135,35,b\n").unwrap();
+
+        let (lat, lon) = crate::from_tokyo(35., 135.).to_jgd2000().degrees().into();
+        let expected = format!("a,{lon:.9},{lat:.9},b,bilinear,0.1\n");
+        assert_eq!(String::from_utf8(ret).unwrap(), expected);
+    }
+
+    #[test]
+    fn east_first() {
+        let jgd2011 = Crs::from_epsg(6668).unwrap();
+        let web_mercator = Crs::from_epsg(3857).unwrap();
+        let transform = CsvTransform::new(jgd2011, web_mercator, ["y", "x"]).precision(2);
+        let ret = run(&transform, b"x,y\n139.8,36\n").unwrap();
+
+        let (x, y) = jgd2011.transform(&web_mercator, (36., 139.8)).unwrap();
+        let expected = format!("x,y\n{x:.2},{y:.2}\n");
+        assert_eq!(String::from_utf8(ret).unwrap(), expected);
+    }
+
+    #[test]
+    fn invalid() {
+        let transform = jgd2011_to_pr9();
+        let err = run(&transform, b"x,y\n1,2\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let err = run(&transform, b"lat,lon\n36,139.8\n36,abc\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 3: invalid coordinate: (36, abc)");
+
+        let err = run(&transform, b"lat,lon,note\n36,139.8,\"a\nb\"\n36\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 4: missing coordinate columns");
+
+        let transform = CsvTransform::new(transform.from, transform.to, [0, 0]);
+        let err = run(&transform, b"lat,lon\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        assert_eq!(run(&jgd2011_to_pr9(), b"").unwrap(), b"");
+    }
+
+    #[test]
+    fn record() {
+        let mut reader = &b"a,\"b\nc\"\r\nd"[..];
+        let mut record = Vec::new();
+        assert!(read_record(&mut reader, &mut record).unwrap());
+        assert_eq!(record, b"a,\"b\nc\"\r\n");
+        assert_eq!(fields(split_eol(&record).0, b','), [0..1, 2..7]);
+        assert!(read_record(&mut reader, &mut record).unwrap());
+        assert_eq!(record, b"d");
+        assert!(!read_record(&mut reader, &mut record).unwrap());
+
+        assert_eq!(unquote(b" \"a\"\"b\" "), &b"a\"b"[..]);
+        assert_eq!(unquote(b"a"), &b"a"[..]);
+    }
+}
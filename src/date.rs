@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+/// 年月日。
+/// Calendar date.
+///
+/// 地震の発生日や、測量の観測日 (元期・今期) を表す。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+impl Date {
+    /// 年月日から変換する。
+    /// Converts from year, month and day.
+    ///
+    /// # Panics
+    ///
+    /// 月が1から12以外、または日がその月に存在しない場合。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Date;
+    ///
+    /// let survey = Date::new(2017, 3, 1);
+    /// assert!(Date::new(2016, 4, 14) < survey);
+    /// ```
+    pub const fn new(year: i32, month: u8, day: u8) -> Self {
+        assert!(1 <= month && month <= 12, "month must be in 1..=12");
+        assert!(
+            1 <= day && day <= Self::days_in_month(year, month),
+            "day must exist in the month"
+        );
+        Self { year, month, day }
+    }
+
+    /// 月の日数。グレゴリオ暦の閏年を考慮する
+    const fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// 年。
+    /// Year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// 月。
+    /// Month.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// 日。
+    /// Day.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order() {
+        assert!(Date::new(2016, 4, 14) < Date::new(2016, 4, 15));
+        assert!(Date::new(2016, 4, 14) < Date::new(2016, 5, 1));
+        assert!(Date::new(2016, 12, 31) < Date::new(2017, 1, 1));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Date::new(2024, 1, 1).to_string(), "2024-01-01");
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_month() {
+        Date::new(2024, 13, 1);
+    }
+
+    #[test]
+    fn leap_year() {
+        assert_eq!(Date::new(2024, 2, 29).day(), 29);
+        assert_eq!(Date::new(2000, 2, 29).day(), 29);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_day() {
+        Date::new(2024, 4, 31);
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_leap_year() {
+        Date::new(2023, 2, 29);
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_leap_century() {
+        Date::new(1900, 2, 29);
+    }
+}
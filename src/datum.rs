@@ -0,0 +1,237 @@
+use crate::{Error, Jgd2000, LatLon, Method, Tokyo97};
+
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+use crate::Interpolation;
+
+#[cfg(feature = "tky2jgd")]
+use crate::Tokyo;
+
+#[cfg(feature = "patchjgd")]
+use crate::Jgd2011;
+
+/// 測地系。
+/// Geodetic datum chosen at runtime.
+///
+/// [`transform`] で、実行時に指定された測地系の間を変換する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Datum {
+    /// [`Tokyo`](crate::Tokyo)
+    Tokyo,
+
+    /// [`Tokyo97`]
+    Tokyo97,
+
+    /// [`Jgd2000`]
+    Jgd2000,
+
+    /// [`Jgd2011`](crate::Jgd2011)
+    Jgd2011,
+}
+impl Datum {
+    const ALL: [Self; 4] = [Self::Tokyo, Self::Tokyo97, Self::Jgd2000, Self::Jgd2011];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 緯度経度を、ある測地系から別の測地系へ変換する。
+/// Transform latitude and longitude between datums chosen at runtime.
+///
+/// 測地系ごとの変換メソッドを、経由する測地系の最も少ない順にたどる。
+/// 例えば [`Datum::Tokyo`] から [`Datum::Jgd2011`] へは [`Tokyo::to_jgd2000`](crate::Tokyo::to_jgd2000) と [`Jgd2000::to_jgd2011`](crate::Jgd2000::to_jgd2011) を、
+/// [`Datum::Jgd2011`] から [`Datum::Tokyo97`] へは [`Jgd2011::to_jgd2000`](crate::Jgd2011::to_jgd2000) と [`Jgd2000::to_tokyo97`] を用いる。
+/// 精度や制約は、それぞれのメソッドと同じ。
+///
+/// # Errors
+///
+/// 有効なフィーチャーで変換できない組み合わせは [`Error::UnsupportedTransformation`] を返す。
+///
+/// # Examples
+///
+/// ```
+/// use jgd::Datum;
+///
+/// # #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+/// # {
+/// let p = jgd::from_tokyo(35.0, 135.0).degrees();
+/// let jgd2011 = jgd::transform(p, Datum::Tokyo, Datum::Jgd2011).unwrap();
+/// # }
+/// ```
+pub fn transform(p: LatLon, from: Datum, to: Datum) -> Result<LatLon, Error> {
+    transform_with_method(p, from, to).map(|(p, _)| p)
+}
+
+/// [`transform`] と同じく変換し、実際に用いられた方法とともに返す。
+/// Transform like [`transform`], and report the method actually applied.
+///
+/// 複数の測地系を経由する場合は、それぞれの変換のうち最も精度の低い方法を返す。
+/// ただし、何も行われなかった変換 [`Method::Identity`] は考慮しない。
+/// 同じ測地系の間では [`Method::Identity`] となる。
+///
+/// [`Datum::Tokyo97`] との変換は [`Method::ThreeParameter`] となる。
+///
+/// # Errors
+///
+/// [`transform`] と同じ。
+///
+/// # Examples
+///
+/// ```
+/// use jgd::{Datum, Method};
+///
+/// # #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+/// # {
+/// let p = jgd::from_tokyo(35.0, 135.0).degrees();
+/// let (_, method) = jgd::transform_with_method(p, Datum::Tokyo, Datum::Jgd2011).unwrap();
+/// assert_eq!(method, Method::Bilinear);
+/// # }
+/// ```
+pub fn transform_with_method(p: LatLon, from: Datum, to: Datum) -> Result<(LatLon, Method), Error> {
+    let steps = path(from, to).ok_or(Error::UnsupportedTransformation { from, to })?;
+    Ok(steps
+        .into_iter()
+        .fold((p, Method::Identity), |(p, method), step| {
+            let (p, m) = step(p);
+            (p, method.coarser(m))
+        }))
+}
+
+type Step = fn(LatLon) -> (LatLon, Method);
+
+/// 測地系の間の変換メソッド
+const STEPS: &[(Datum, Datum, Step)] = &[
+    #[cfg(feature = "tky2jgd")]
+    (Datum::Tokyo, Datum::Jgd2000, |p| {
+        let (p, method) = Tokyo::new(p).to_jgd2000_with_method(Interpolation::Bilinear);
+        (p.degrees(), method)
+    }),
+    #[cfg(feature = "tky2jgd")]
+    (Datum::Jgd2000, Datum::Tokyo, |p| {
+        let (p, method) = Jgd2000::new(p).to_tokyo_with_method();
+        (p.degrees(), method)
+    }),
+    (Datum::Tokyo97, Datum::Jgd2000, |p| {
+        let p = Tokyo97::new(p).to_jgd2000().degrees();
+        (p, Method::ThreeParameter)
+    }),
+    (Datum::Jgd2000, Datum::Tokyo97, |p| {
+        let p = Jgd2000::new(p).to_tokyo97().lat_lon();
+        (p, Method::ThreeParameter)
+    }),
+    #[cfg(feature = "patchjgd")]
+    (Datum::Jgd2000, Datum::Jgd2011, |p| {
+        let (p, method) = Jgd2000::new(p).to_jgd2011_with_method(Interpolation::Bilinear);
+        (p.degrees(), method)
+    }),
+    #[cfg(feature = "patchjgd")]
+    (Datum::Jgd2011, Datum::Jgd2000, |p| {
+        let (p, method) = Jgd2011::new(p).to_jgd2000_with_method();
+        (p.degrees(), method)
+    }),
+];
+
+/// 幅優先探索で、最も短い変換メソッドの列を求める。
+fn path(from: Datum, to: Datum) -> Option<Vec<Step>> {
+    // 各測地系に至る直前の変換
+    let mut prev: [Option<(Datum, Step)>; Datum::ALL.len()] = [None; Datum::ALL.len()];
+    let mut queue = std::collections::VecDeque::from([from]);
+    while let Some(datum) = queue.pop_front() {
+        if datum == to {
+            break;
+        }
+        for &(a, b, step) in STEPS {
+            if a == datum && b != from && prev[b.index()].is_none() {
+                prev[b.index()] = Some((a, step));
+                queue.push_back(b);
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    let mut datum = to;
+    while datum != from {
+        let (a, step) = prev[datum.index()]?;
+        steps.push(step);
+        datum = a;
+    }
+    steps.reverse();
+    Some(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity() {
+        let p = LatLon::new(35., 135.);
+        for datum in Datum::ALL {
+            assert_eq!(transform(p, datum, datum), Ok(p));
+        }
+    }
+
+    #[test]
+    fn tokyo97() {
+        let p = LatLon::new(35., 135.);
+        let ret = transform(p, Datum::Tokyo97, Datum::Jgd2000).unwrap();
+        assert_eq!(ret, Tokyo97::new(p).to_jgd2000().degrees());
+    }
+
+    #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+    #[test]
+    fn via_jgd2000() {
+        let p = LatLon::new(38.26, 140.87);
+        let ret = transform(p, Datum::Tokyo, Datum::Jgd2011).unwrap();
+        assert_eq!(ret, Tokyo::new(p).to_jgd2000().to_jgd2011().degrees());
+
+        let ret = transform(p, Datum::Jgd2011, Datum::Tokyo97).unwrap();
+        assert_eq!(ret, Jgd2011::new(p).to_jgd2000().to_tokyo97().lat_lon());
+
+        for from in Datum::ALL {
+            for to in Datum::ALL {
+                assert!(transform(p, from, to).is_ok(), "{from:?} -> {to:?}");
+            }
+        }
+    }
+
+    #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+    #[test]
+    fn method() {
+        let p = LatLon::new(35., 135.);
+        let ret = transform_with_method(p, Datum::Jgd2011, Datum::Jgd2011);
+        assert_eq!(ret, Ok((p, Method::Identity)));
+
+        // 東北地方太平洋沖地震の影響がない地域
+        let (_, method) = transform_with_method(p, Datum::Tokyo, Datum::Jgd2011).unwrap();
+        assert_eq!(method, Method::Bilinear);
+        let (_, method) = transform_with_method(p, Datum::Jgd2011, Datum::Jgd2000).unwrap();
+        assert_eq!(method, Method::Identity);
+        let (_, method) = transform_with_method(p, Datum::Tokyo97, Datum::Tokyo).unwrap();
+        assert_eq!(method, Method::ThreeParameter);
+
+        // パラメータグリッドの範囲外
+        let p = LatLon::new(20., 120.);
+        let (ret, method) = transform_with_method(p, Datum::Jgd2000, Datum::Tokyo).unwrap();
+        assert_eq!(ret, Jgd2000::new(p).to_tokyo().degrees());
+        assert_eq!(method, Method::ThreeParameter);
+
+        let p = LatLon::new(38.26, 140.87);
+        let (ret, method) = transform_with_method(p, Datum::Jgd2011, Datum::Jgd2000).unwrap();
+        assert_eq!(ret, Jgd2011::new(p).to_jgd2000().degrees());
+        assert_eq!(method, Method::Bilinear);
+    }
+
+    #[cfg(not(feature = "tky2jgd"))]
+    #[test]
+    fn unsupported() {
+        let p = LatLon::new(35., 135.);
+        assert_eq!(
+            transform(p, Datum::Tokyo, Datum::Jgd2000),
+            Err(Error::UnsupportedTransformation {
+                from: Datum::Tokyo,
+                to: Datum::Jgd2000
+            })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{coord::ECEF, LatLon};
+use crate::{coord::ECEF, LatLon, LatLonHeight};
 
 /// GRS80楕円体
 pub const GRS80: Ellipsoid = Ellipsoid {
@@ -23,26 +23,41 @@ pub struct Ellipsoid {
 }
 impl Ellipsoid {
     /// 三次元直交座標に変換する。
-    pub fn to_ecef(&self, degree: LatLon) -> ECEF {
-        let (lat, lon) = degree.map(f64::to_radians).into();
-        let geoid = self.equatorial_radius
-            / (1.0 - self.equatorial_eccentricity() * lat.sin().powi(2)).sqrt();
+    pub fn to_ecef(&self, p: LatLonHeight) -> ECEF {
+        let (lat, lon) = p.lat_lon().map(f64::to_radians).into();
+        let geoid = self.prime_vertical_radius(lat);
         ECEF::new(
-            geoid * lat.cos() * lon.cos(),
-            geoid * lat.cos() * lon.sin(),
-            geoid * (1.0 - self.equatorial_eccentricity()) * lat.sin(),
+            (geoid + p.height()) * lat.cos() * lon.cos(),
+            (geoid + p.height()) * lat.cos() * lon.sin(),
+            (geoid * (1.0 - self.equatorial_eccentricity()) + p.height()) * lat.sin(),
         )
     }
 
     /// 測地座標に変換する。
-    pub fn to_geodetic(&self, ecef: ECEF) -> LatLon {
+    pub fn to_geodetic(&self, ecef: ECEF) -> LatLonHeight {
         let p = ecef.x().hypot(ecef.y());
         let theta = ((ecef.z() * self.equatorial_radius) / (p * self.polar_radius)).atan();
         let lat = (ecef.z()
             + self.polar_eccentricity() * self.polar_radius * (theta.sin().powi(3)))
         .atan2(p - self.equatorial_eccentricity() * self.equatorial_radius * (theta.cos().powi(3)));
         let lon = ecef.y().atan2(ecef.x());
-        LatLon::new(lat.to_degrees(), lon.to_degrees())
+        let height = p / lat.cos() - self.prime_vertical_radius(lat);
+        LatLonHeight::new(LatLon::new(lat.to_degrees(), lon.to_degrees()), height)
+    }
+
+    /// 卯酉線曲率半径 (メートル)
+    fn prime_vertical_radius(&self, lat: f64) -> f64 {
+        self.equatorial_radius / (1.0 - self.equatorial_eccentricity() * lat.sin().powi(2)).sqrt()
+    }
+
+    /// 赤道半径 (メートル)
+    pub fn equatorial_radius(&self) -> f64 {
+        self.equatorial_radius
+    }
+
+    /// 第三扁平率 = (赤道半径 - 極半径) / (赤道半径 + 極半径)
+    pub fn third_flattening(&self) -> f64 {
+        (self.equatorial_radius - self.polar_radius) / (self.equatorial_radius + self.polar_radius)
     }
 
     /// 赤道離心率 = (赤道半径^2 - 極半径^2) / 赤道半径^2
@@ -62,14 +77,25 @@ impl Ellipsoid {
 
 #[cfg(test)]
 mod tests {
-    use approx::assert_ulps_eq;
+    use approx::{assert_abs_diff_eq, assert_ulps_eq};
 
     use super::{BESSEL, GRS80};
+    use crate::{LatLon, LatLonHeight};
 
     #[test]
     fn grs80() {
         assert_ulps_eq!(GRS80.equatorial_eccentricity(), 0.006694379990141124);
         assert_ulps_eq!(GRS80.polar_eccentricity(), 0.006739496742276239);
+        assert_ulps_eq!(GRS80.third_flattening(), 0.0016792203863836474);
+    }
+
+    #[test]
+    fn ecef_round_trip() {
+        let p = LatLonHeight::new(LatLon::new(35.0, 135.0), 3776.0);
+        let (lat, lon, height) = GRS80.to_geodetic(GRS80.to_ecef(p)).into();
+        assert_abs_diff_eq!(lat, 35.0, epsilon = 1e-10);
+        assert_abs_diff_eq!(lon, 135.0, epsilon = 1e-10);
+        assert_abs_diff_eq!(height, 3776.0, epsilon = 1e-4);
     }
 
     #[test]
@@ -0,0 +1,348 @@
+use std::fmt::Display;
+
+use crate::{
+    crs::Geographic, Datum, Error, Jgd2000, Jgd2011, LatLon, Method, PlaneRectangular, Tokyo, Utm,
+    WebMercator, Zone,
+};
+
+/// 座標参照系の投影法。
+/// Projection of a coordinate reference system.
+///
+/// [`Crs::transform`] で扱う座標の順序は、それぞれの型から `(f64, f64)` に変換した場合と同じ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Projection {
+    /// 投影しない地理座標。緯度, 経度 (度) の順。
+    /// Geographic latitude and longitude in degrees.
+    Geographic,
+
+    /// [`PlaneRectangular`] の系。北向きのX, 東向きのY (メートル) の順。
+    /// Zone of the Japan Plane Rectangular Coordinate System.
+    PlaneRectangular(Zone),
+
+    /// [`Utm`] の帯番号。東向き, 北向き (メートル) の順。
+    /// Zone number of UTM.
+    Utm(u8),
+
+    /// [`WebMercator`]。東向きのX, 北向きのY (メートル) の順。
+    /// Web Mercator projection.
+    WebMercator,
+}
+
+/// EPSGコードで識別される座標参照系。
+/// Coordinate reference system identified by an EPSG code.
+///
+/// 次の測地系と投影法の組み合わせが登録されている。
+///
+/// | EPSG | 測地系 | 投影法 |
+/// |---|---|---|
+/// | 4301 | [`Tokyo`] | 地理座標 |
+/// | 4612 | [`Jgd2000`] | 地理座標 |
+/// | 6668 | [`Jgd2011`] | 地理座標 |
+/// | 30161 - 30179 | [`Tokyo`] | 平面直角座標系 第I系 - 第XIX系 |
+/// | 2443 - 2461 | [`Jgd2000`] | 平面直角座標系 第I系 - 第XIX系 |
+/// | 6669 - 6687 | [`Jgd2011`] | 平面直角座標系 第I系 - 第XIX系 |
+/// | 3097 - 3101 | [`Jgd2000`] | UTM 51帯 - 55帯 |
+/// | 6688 - 6692 | [`Jgd2011`] | UTM 51帯 - 55帯 |
+/// | 3857 | [`Jgd2011`] | Webメルカトル |
+///
+/// [`Tokyo97`](crate::Tokyo97) にはEPSGコードがない。
+/// Webメルカトルは本来WGS84を基準とするが、[`WebMercator`] と同じく世界測地系と区別しない。
+///
+/// 文字列に変換すると `EPSG:6668` のようになる。
+///
+/// # Examples
+///
+/// ```
+/// use jgd::Crs;
+///
+/// // JGD2000 平面直角座標系 第IX系 から JGD2011 平面直角座標系 第IX系
+/// let from = Crs::from_epsg(2451).unwrap();
+/// let to = Crs::from_epsg(6677).unwrap();
+/// #[cfg(feature = "patchjgd")]
+/// let (x, y) = from.transform(&to, (-3000.0, 10000.0)).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crs {
+    base: Base,
+    projection: Projection,
+    epsg: u32,
+}
+impl Crs {
+    /// 測地系と投影法から作成する。
+    /// Creates from a datum and a projection.
+    ///
+    /// EPSGコードが登録されていない組み合わせは `None` を返す。
+    pub fn new(datum: Datum, projection: Projection) -> Option<Self> {
+        let base = Base::from_datum(datum)?;
+        let epsg = base.epsg(projection)?;
+        Some(Self {
+            base,
+            projection,
+            epsg,
+        })
+    }
+
+    /// EPSGコードから作成する。
+    /// Looks up an EPSG code.
+    ///
+    /// 登録されていないコードは `None` を返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::{Crs, Datum, Projection, Zone};
+    ///
+    /// let crs = Crs::from_epsg(6677).unwrap();
+    /// assert_eq!(crs.datum(), Datum::Jgd2011);
+    /// assert_eq!(crs.projection(), Projection::PlaneRectangular(Zone::IX));
+    /// assert_eq!(Crs::from_epsg(4326), None);
+    /// ```
+    pub fn from_epsg(code: u32) -> Option<Self> {
+        Self::all().find(|crs| crs.epsg == code)
+    }
+
+    /// EPSGコード。
+    /// EPSG code.
+    pub fn epsg(&self) -> u32 {
+        self.epsg
+    }
+
+    /// 測地系。
+    /// Geodetic datum.
+    pub fn datum(&self) -> Datum {
+        self.base.datum()
+    }
+
+    /// 投影法。
+    /// Projection.
+    pub fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    pub(crate) fn base(&self) -> Base {
+        self.base
+    }
+
+    /// 座標を、別の座標参照系へ変換する。
+    /// Transform a coordinate to another coordinate reference system.
+    ///
+    /// 座標の順序は [`Projection`] を参照。
+    /// 逆投影し、[`transform`](crate::transform) で測地系を変換し、投影する。
+    ///
+    /// # Errors
+    ///
+    /// 有効なフィーチャーで測地系を変換できない場合は [`Error::UnsupportedTransformation`] を返す。
+    pub fn transform(&self, to: &Crs, coord: (f64, f64)) -> Result<(f64, f64), Error> {
+        self.transform_with_method(to, coord)
+            .map(|(coord, _)| coord)
+    }
+
+    /// [`Crs::transform`] と同じく変換し、測地系の変換に実際に用いられた方法とともに返す。
+    /// Transform like [`Crs::transform`], and report the method actually applied.
+    ///
+    /// 方法は [`transform_with_method`](crate::transform_with_method) と同じ。
+    ///
+    /// # Errors
+    ///
+    /// [`Crs::transform`] と同じ。
+    pub fn transform_with_method(
+        &self,
+        to: &Crs,
+        coord: (f64, f64),
+    ) -> Result<((f64, f64), Method), Error> {
+        let degrees = self.base.unproject(self.projection, coord);
+        let (degrees, method) = crate::transform_with_method(degrees, self.datum(), to.datum())?;
+        Ok((to.base.project(to.projection, degrees), method))
+    }
+
+    /// 登録されているすべての座標参照系
+    fn all() -> impl Iterator<Item = Self> {
+        let projections = [Projection::Geographic, Projection::WebMercator]
+            .into_iter()
+            .chain(
+                (1..=19)
+                    .filter_map(Zone::new)
+                    .map(Projection::PlaneRectangular),
+            )
+            .chain((51..=55).map(Projection::Utm));
+        projections.flat_map(|projection| {
+            Base::ALL.into_iter().filter_map(move |base| {
+                let epsg = base.epsg(projection)?;
+                Some(Self {
+                    base,
+                    projection,
+                    epsg,
+                })
+            })
+        })
+    }
+}
+impl Display for Crs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EPSG:{}", self.epsg)
+    }
+}
+
+/// EPSGコードのある測地系
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Base {
+    Tokyo,
+    Jgd2000,
+    Jgd2011,
+}
+impl Base {
+    const ALL: [Self; 3] = [Self::Tokyo, Self::Jgd2000, Self::Jgd2011];
+
+    fn from_datum(datum: Datum) -> Option<Self> {
+        match datum {
+            Datum::Tokyo => Some(Self::Tokyo),
+            Datum::Tokyo97 => None,
+            Datum::Jgd2000 => Some(Self::Jgd2000),
+            Datum::Jgd2011 => Some(Self::Jgd2011),
+        }
+    }
+
+    fn datum(self) -> Datum {
+        match self {
+            Self::Tokyo => Datum::Tokyo,
+            Self::Jgd2000 => Datum::Jgd2000,
+            Self::Jgd2011 => Datum::Jgd2011,
+        }
+    }
+
+    fn epsg(self, projection: Projection) -> Option<u32> {
+        match self {
+            Self::Tokyo => epsg::<Tokyo>(projection),
+            Self::Jgd2000 => epsg::<Jgd2000>(projection),
+            Self::Jgd2011 => epsg::<Jgd2011>(projection),
+        }
+    }
+
+    fn unproject(self, projection: Projection, coord: (f64, f64)) -> LatLon {
+        match self {
+            Self::Tokyo => unproject::<Tokyo>(projection, coord),
+            Self::Jgd2000 => unproject::<Jgd2000>(projection, coord),
+            Self::Jgd2011 => unproject::<Jgd2011>(projection, coord),
+        }
+    }
+
+    fn project(self, projection: Projection, degrees: LatLon) -> (f64, f64) {
+        match self {
+            Self::Tokyo => project::<Tokyo>(projection, degrees),
+            Self::Jgd2000 => project::<Jgd2000>(projection, degrees),
+            Self::Jgd2011 => project::<Jgd2011>(projection, degrees),
+        }
+    }
+}
+
+fn epsg<T: Geographic>(projection: Projection) -> Option<u32> {
+    match projection {
+        Projection::Geographic => Some(T::EPSG),
+        Projection::PlaneRectangular(zone) => Some(PlaneRectangular::<T>::new(zone, 0., 0.).epsg()),
+        Projection::Utm(zone) => (51..=55)
+            .contains(&zone)
+            .then(|| Utm::<T>::new(zone, 0., 0.).epsg())?,
+        Projection::WebMercator => (T::EPSG == Jgd2011::EPSG).then_some(WebMercator::<T>::EPSG),
+    }
+}
+
+fn unproject<T: Geographic>(projection: Projection, (a, b): (f64, f64)) -> LatLon {
+    match projection {
+        Projection::Geographic => LatLon::new(a, b),
+        Projection::PlaneRectangular(zone) => PlaneRectangular::<T>::new(zone, a, b)
+            .to_geographic()
+            .degrees(),
+        Projection::Utm(zone) => Utm::<T>::new(zone, a, b).to_geographic().degrees(),
+        Projection::WebMercator => WebMercator::<T>::new(a, b).to_geographic().degrees(),
+    }
+}
+
+fn project<T: Geographic>(projection: Projection, degrees: LatLon) -> (f64, f64) {
+    match projection {
+        Projection::Geographic => degrees.into(),
+        Projection::PlaneRectangular(zone) => {
+            PlaneRectangular::<T>::from_degrees(zone, degrees).into()
+        }
+        Projection::Utm(zone) => Utm::<T>::from_degrees_in(zone, degrees).into(),
+        Projection::WebMercator => WebMercator::<T>::from_degrees(degrees).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn from_epsg() {
+        for (code, datum, projection) in [
+            (4301, Datum::Tokyo, Projection::Geographic),
+            (4612, Datum::Jgd2000, Projection::Geographic),
+            (6668, Datum::Jgd2011, Projection::Geographic),
+            (30169, Datum::Tokyo, Projection::PlaneRectangular(Zone::IX)),
+            (2451, Datum::Jgd2000, Projection::PlaneRectangular(Zone::IX)),
+            (6677, Datum::Jgd2011, Projection::PlaneRectangular(Zone::IX)),
+            (
+                6687,
+                Datum::Jgd2011,
+                Projection::PlaneRectangular(Zone::XIX),
+            ),
+            (3097, Datum::Jgd2000, Projection::Utm(51)),
+            (6692, Datum::Jgd2011, Projection::Utm(55)),
+            (3857, Datum::Jgd2011, Projection::WebMercator),
+        ] {
+            let sut = Crs::from_epsg(code).unwrap();
+            assert_eq!((sut.datum(), sut.projection()), (datum, projection));
+            assert_eq!(Crs::new(datum, projection), Some(sut));
+            assert_eq!(sut.epsg(), code);
+        }
+        assert_eq!(Crs::all().count(), 3 + 3 * 19 + 2 * 5 + 1);
+    }
+
+    #[test]
+    fn unregistered() {
+        for code in [0, 4326, 2442, 3096, 6693, 30160] {
+            assert_eq!(Crs::from_epsg(code), None, "{code}");
+        }
+        assert_eq!(Crs::new(Datum::Tokyo97, Projection::Geographic), None);
+        assert_eq!(Crs::new(Datum::Tokyo, Projection::Utm(54)), None);
+        assert_eq!(Crs::new(Datum::Jgd2011, Projection::Utm(56)), None);
+        assert_eq!(Crs::new(Datum::Jgd2000, Projection::WebMercator), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Crs::from_epsg(6668).unwrap().to_string(), "EPSG:6668");
+    }
+
+    #[test]
+    fn projection_only() {
+        let from = Crs::from_epsg(6677).unwrap();
+        let to = Crs::from_epsg(6668).unwrap();
+        let (lat, lon) = from.transform(&to, (-3000., 10000.)).unwrap();
+        let exp = PlaneRectangular::<Jgd2011>::new(Zone::IX, -3000., 10000.)
+            .to_geographic()
+            .degrees();
+        assert_eq!(LatLon::new(lat, lon), exp);
+
+        let (x, y) = to.transform(&from, (lat, lon)).unwrap();
+        assert_abs_diff_eq!(x, -3000., epsilon = 1e-6);
+        assert_abs_diff_eq!(y, 10000., epsilon = 1e-6);
+    }
+
+    #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+    #[test]
+    fn datum_and_projection() {
+        let from = Crs::from_epsg(30169).unwrap();
+        let to = Crs::from_epsg(6692).unwrap();
+        let ret = from.transform(&to, (-3000., 10000.)).unwrap();
+        let exp = PlaneRectangular::<Tokyo>::new(Zone::IX, -3000., 10000.)
+            .to_geographic()
+            .to_jgd2000()
+            .to_jgd2011()
+            .to_utm_zone(55)
+            .into();
+        assert_eq!(ret, exp);
+    }
+}
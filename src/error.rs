@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use crate::{Datum, LatLon, LatLonHeight};
+
+/// 座標変換などのエラー。
+/// Error of a coordinate transformation and others.
+///
+/// `try_` で始まる変換メソッドや、[`transform`](crate::transform), [`Mesh`](crate::Mesh) の変換が返す。
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// 座標がパラメータグリッドの範囲外。
+    /// The coordinate is outside the parameters grid.
+    OutsideGrid(LatLon),
+
+    /// 座標が NaN や無限大、または緯度の絶対値が90度を超える。
+    /// The coordinate is NaN, infinite, or its latitude is out of range.
+    InvalidCoordinate(LatLonHeight),
+
+    /// 逆変換の反復計算が収束しない。
+    /// Iteration of the inverse transformation does not converge.
+    NotConverged(LatLon),
+
+    /// メッシュコードが不正。
+    /// The mesh code is invalid.
+    InvalidMeshCode,
+
+    /// 有効なフィーチャーでは変換できない測地系の組み合わせ。
+    /// The transformation between the datums is not supported with the enabled features.
+    UnsupportedTransformation {
+        /// 変換元
+        from: Datum,
+        /// 変換先
+        to: Datum,
+    },
+}
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutsideGrid(p) => {
+                write!(f, "outside the grid: ({}, {})", p.lat(), p.lon())
+            }
+            Self::InvalidCoordinate(p) => write!(
+                f,
+                "invalid coordinate: ({}, {}, {})",
+                p.lat_lon().lat(),
+                p.lat_lon().lon(),
+                p.height()
+            ),
+            Self::NotConverged(p) => {
+                write!(f, "inverse did not converge: ({}, {})", p.lat(), p.lon())
+            }
+            Self::InvalidMeshCode => write!(f, "invalid mesh code"),
+            Self::UnsupportedTransformation { from, to } => {
+                write!(f, "unsupported transformation: {:?} to {:?}", from, to)
+            }
+        }
+    }
+}
+impl std::error::Error for Error {}
+
+/// 緯度経度と楕円体高が有限で、緯度の絶対値が90度以下であることを検査する。
+pub(crate) fn check(p: LatLonHeight) -> Result<(), Error> {
+    let (lat, lon) = (p.lat_lon().lat(), p.lat_lon().lon());
+    if lat.is_finite() && lon.is_finite() && p.height().is_finite() && lat.abs() <= 90. {
+        Ok(())
+    } else {
+        Err(Error::InvalidCoordinate(p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_coordinate() {
+        let valid = LatLonHeight::new(LatLon::new(-90., 180.), 0.);
+        assert_eq!(check(valid), Ok(()));
+
+        for (lat, lon, height) in [
+            (f64::NAN, 135., 0.),
+            (35., f64::INFINITY, 0.),
+            (35., 135., f64::NAN),
+            (90.1, 135., 0.),
+        ] {
+            let p = LatLonHeight::new(LatLon::new(lat, lon), height);
+            assert!(matches!(check(p), Err(Error::InvalidCoordinate(_))));
+        }
+    }
+
+    #[test]
+    fn display() {
+        let e = Error::OutsideGrid(LatLon::new(35., 135.));
+        assert_eq!(e.to_string(), "outside the grid: (35, 135)");
+    }
+}
@@ -0,0 +1,473 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+use crate::LatLon;
+
+/// ジオイド・モデル。
+/// Geoid model.
+///
+/// 国土地理院が提供する格子状のジオイド高を、実行時にファイルから読み込む。
+/// 楕円体高と標高 (東京湾平均海面からの高さ) を相互に変換できる。
+///
+/// 対応する形式は以下の通り。
+///
+/// - `gsigeo2011_ver2_2.asc` などの日本のジオイド2011 (ASCII形式)
+/// - `JPGEO2024` などのISG形式
+///
+/// 緯度経度は [`Jgd2011`](crate::Jgd2011) とする。
+///
+/// # Examples
+///
+/// ```no_run
+/// use jgd::Geoid;
+///
+/// let geoid = Geoid::open("gsigeo2011_ver2_2.asc")?;
+/// let p = jgd::from_jgd2011(35.0, 135.0).degrees();
+/// let elevation = geoid.orthometric_height(p, 100.0);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Geoid {
+    /// 南西端の格子点の緯度経度
+    south_west: LatLon,
+
+    /// 格子の間隔
+    step: LatLon,
+
+    /// 南北の格子点数
+    rows: usize,
+
+    /// 東西の格子点数
+    cols: usize,
+
+    /// 南から北、西から東の順に並べたジオイド高 (メートル)。欠測値はNaN。
+    heights: Vec<f32>,
+}
+impl Geoid {
+    /// 日本のジオイド2011の欠測値
+    const ASC_NODATA: f64 = 999.;
+
+    /// ファイルから読み込む。
+    /// Loads from a file.
+    ///
+    /// ISG形式のヘッダーがあればISG形式、なければASCII形式として読み込む。
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        if reader.fill_buf()?.starts_with(b"begin_of_head") {
+            Self::from_isg(reader)
+        } else {
+            Self::from_asc(reader)
+        }
+    }
+
+    /// 日本のジオイド2011のASCII形式から読み込む。
+    /// Loads from the ASCII format of GSIGEO2011.
+    ///
+    /// 1行目はヘッダーで、南端の緯度、西端の経度、緯度間隔、経度間隔、緯度方向の格子点数、経度方向の格子点数、形式、バージョン。
+    /// 以降は南から北へ、西から東へ並べたジオイド高で、`999.0000` は欠測値。
+    pub fn from_asc(reader: impl Read) -> io::Result<Self> {
+        let mut lines = BufReader::new(reader).lines();
+        let header = lines.next().ok_or_else(|| invalid("missing header"))??;
+        let mut header = header.split_whitespace();
+        let mut field = || header.next().ok_or_else(|| invalid("short header"));
+        let lat: f64 = parse(field()?)?;
+        let lon: f64 = parse(field()?)?;
+        let dlat: f64 = parse(field()?)?;
+        let dlon: f64 = parse(field()?)?;
+        let rows: usize = parse(field()?)?;
+        let cols: usize = parse(field()?)?;
+
+        let mut heights = Vec::with_capacity(rows * cols);
+        for line in lines {
+            for value in line?.split_whitespace() {
+                let value: f64 = parse(value)?;
+                heights.push(nodata_to_nan(value, Some(Self::ASC_NODATA)));
+            }
+        }
+
+        let step = LatLon::new(dlat, dlon).map(snap);
+        Self::new(LatLon::new(lat, lon), step, rows, cols, heights)
+    }
+
+    /// ISG形式から読み込む。
+    /// Loads from the ISG format.
+    ///
+    /// 角度は十進の度、または度分秒 (`120°00'00"`) で表される。
+    /// ジオイド高は北から南へ、西から東へ並ぶ。
+    /// 範囲と格子間隔、格子点数のヘッダーは必須。`nodata` が無い場合は欠測値が無いものとする。
+    pub fn from_isg(reader: impl Read) -> io::Result<Self> {
+        let mut lines = BufReader::new(reader).lines();
+        let mut header = IsgHeader::default();
+        loop {
+            let line = lines
+                .next()
+                .ok_or_else(|| invalid("missing end_of_head"))??;
+            if line.starts_with("end_of_head") {
+                break;
+            }
+            if let Some((key, value)) = line.split_once([':', '=']) {
+                header.set(key.trim(), value.trim())?;
+            }
+        }
+        let rows = required(header.nrows, "nrows")?;
+        let cols = required(header.ncols, "ncols")?;
+        let min = LatLon::new(
+            required(header.lat_min, "lat min")?,
+            required(header.lon_min, "lon min")?,
+        );
+        let max = LatLon::new(
+            required(header.lat_max, "lat max")?,
+            required(header.lon_max, "lon max")?,
+        );
+        let step = LatLon::new(
+            required(header.delta_lat, "delta lat")?,
+            required(header.delta_lon, "delta lon")?,
+        )
+        .map(snap);
+
+        // 北の行から順に並んでいるため、行単位で反転する
+        let mut north_first = Vec::with_capacity(rows * cols);
+        for line in lines {
+            for value in line?.split_whitespace() {
+                let value: f64 = parse(value)?;
+                north_first.push(nodata_to_nan(value, header.nodata));
+            }
+        }
+        if north_first.len() != rows * cols {
+            return Err(invalid("number of values does not match the header"));
+        }
+        let heights = north_first.chunks(cols).rev().flatten().copied().collect();
+
+        // 範囲が格子点ではなく格子の外縁を表す場合は、半格子ずらす
+        let nodes = ((max.lat() - min.lat()) / step.lat()).round() as usize;
+        let south_west = if nodes + 1 == rows {
+            min
+        } else {
+            min + step / 2.
+        };
+        Self::new(south_west, step, rows, cols, heights)
+    }
+
+    fn new(
+        south_west: LatLon,
+        step: LatLon,
+        rows: usize,
+        cols: usize,
+        heights: Vec<f32>,
+    ) -> io::Result<Self> {
+        if rows < 2 || cols < 2 || !(step.lat() > 0. && step.lon() > 0.) {
+            return Err(invalid("invalid grid size"));
+        }
+        if heights.len() != rows * cols {
+            return Err(invalid("number of values does not match the header"));
+        }
+        Ok(Self {
+            south_west,
+            step,
+            rows,
+            cols,
+            heights,
+        })
+    }
+
+    /// ジオイド高 (メートル)。
+    /// Geoid undulation in meters.
+    ///
+    /// 周囲4つの格子点から双線形補間する。
+    /// 範囲外や、周囲に欠測値がある場合は `None` を返す。
+    pub fn undulation(&self, p: LatLon) -> Option<f64> {
+        let (i, y) = Self::locate(
+            (p.lat() - self.south_west.lat()) / self.step.lat(),
+            self.rows,
+        )?;
+        let (j, x) = Self::locate(
+            (p.lon() - self.south_west.lon()) / self.step.lon(),
+            self.cols,
+        )?;
+
+        let at = |i: usize, j: usize| f64::from(self.heights[i * self.cols + j]);
+        let sw = at(i, j);
+        let se = at(i, j + 1);
+        let nw = at(i + 1, j);
+        let ne = at(i + 1, j + 1);
+
+        let n = (1. - y) * ((1. - x) * sw + x * se) + y * ((1. - x) * nw + x * ne);
+        (!n.is_nan()).then_some(n)
+    }
+
+    /// 楕円体高から標高に変換する。
+    /// Converts an ellipsoidal height into an orthometric height.
+    ///
+    /// 標高 = 楕円体高 - ジオイド高
+    pub fn orthometric_height(&self, p: LatLon, ellipsoidal_height: f64) -> Option<f64> {
+        Some(ellipsoidal_height - self.undulation(p)?)
+    }
+
+    /// 標高から楕円体高に変換する。
+    /// Converts an orthometric height into an ellipsoidal height.
+    ///
+    /// 楕円体高 = 標高 + ジオイド高
+    pub fn ellipsoidal_height(&self, p: LatLon, orthometric_height: f64) -> Option<f64> {
+        Some(orthometric_height + self.undulation(p)?)
+    }
+
+    /// 格子番号と、格子内の位置 (0以上1以下)
+    fn locate(t: f64, len: usize) -> Option<(usize, f64)> {
+        // 端の格子点上の座標が、丸め誤差で範囲外とならないようにする
+        const EPSILON: f64 = 1e-9;
+        let last = (len - 1) as f64;
+        if !(-EPSILON..=last + EPSILON).contains(&t) {
+            return None;
+        }
+        let t = t.clamp(0., last);
+        let i = (t.floor() as usize).min(len - 2);
+        Some((i, t - i as f64))
+    }
+}
+
+/// ISG形式のヘッダー
+#[derive(Debug, Default)]
+struct IsgHeader {
+    lat_min: Option<f64>,
+    lat_max: Option<f64>,
+    lon_min: Option<f64>,
+    lon_max: Option<f64>,
+    delta_lat: Option<f64>,
+    delta_lon: Option<f64>,
+    nrows: Option<usize>,
+    ncols: Option<usize>,
+    nodata: Option<f64>,
+}
+impl IsgHeader {
+    fn set(&mut self, key: &str, value: &str) -> io::Result<()> {
+        match key {
+            "lat min" => self.lat_min = Some(parse_angle(value)?),
+            "lat max" => self.lat_max = Some(parse_angle(value)?),
+            "lon min" => self.lon_min = Some(parse_angle(value)?),
+            "lon max" => self.lon_max = Some(parse_angle(value)?),
+            "delta lat" => self.delta_lat = Some(parse_angle(value)?),
+            "delta lon" => self.delta_lon = Some(parse_angle(value)?),
+            "nrows" => self.nrows = Some(parse(value)?),
+            "ncols" => self.ncols = Some(parse(value)?),
+            "nodata" => self.nodata = Some(parse(value)?),
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+/// 必須のヘッダーの値
+fn required<T>(value: Option<T>, key: &str) -> io::Result<T> {
+    value.ok_or_else(|| invalid(format!("missing {}", key)))
+}
+
+/// 十進の度、または度分秒 (`d°m's"`) の角度
+fn parse_angle(s: &str) -> io::Result<f64> {
+    let Some((d, rest)) = s.split_once('°') else {
+        return parse(s);
+    };
+    let (m, rest) = rest.split_once('\'').unwrap_or((rest, ""));
+    let s = rest.trim_end_matches('"');
+    let d: f64 = parse(d)?;
+    let m: f64 = if m.trim().is_empty() { 0. } else { parse(m)? };
+    let s: f64 = if s.trim().is_empty() { 0. } else { parse(s)? };
+    let abs = d.abs() + m / 60. + s / 3_600.;
+    Ok(if d.is_sign_negative() { -abs } else { abs })
+}
+
+/// `0.016667` のように丸められた格子間隔を、0.01秒単位に揃える
+fn snap(degrees: f64) -> f64 {
+    (degrees * 360_000.).round() / 360_000.
+}
+
+/// 欠測値を NaN とする。`nodata` が無い場合は変換しない
+fn nodata_to_nan(value: f64, nodata: Option<f64>) -> f32 {
+    if Some(value) == nodata {
+        f32::NAN
+    } else {
+        value as f32
+    }
+}
+
+fn parse<T: std::str::FromStr>(s: &str) -> io::Result<T> {
+    s.trim()
+        .parse()
+        .map_err(|_| invalid(format!("invalid number: {}", s)))
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    /// 南北3点、東西4点
+    const ASC: &str = "\
+  35.00000 135.00000 0.016667 0.025000 3 4 1 ver2.2
+  36.0000  36.1000  36.2000  36.3000
+  37.0000  37.1000  37.2000 999.0000
+  38.0000  38.1000  38.2000  38.3000
+";
+
+    const ISG: &str = "\
+begin_of_head ================================================
+model name     : TEST
+data type      : geoid
+coord units    : dms
+lat min        :  35°00'00\"
+lat max        :  35°02'00\"
+lon min        : 135°00'00\"
+lon max        : 135°04'30\"
+delta lat      :   0°01'00\"
+delta lon      :   0°01'30\"
+nrows          :          3
+ncols          :          4
+nodata         : -9999.0000
+ISG format     : 2.0
+end_of_head ==================================================
+   38.0000    38.1000    38.2000    38.3000
+   37.0000    37.1000    37.2000 -9999.0000
+   36.0000    36.1000    36.2000    36.3000
+";
+
+    fn asc() -> Geoid {
+        Geoid::from_asc(ASC.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn grid_point() {
+        let sut = asc();
+        let ret = sut.undulation(LatLon::new(35., 135.)).unwrap();
+        assert_abs_diff_eq!(ret, 36.0, epsilon = 1e-5);
+        let ret = sut
+            .undulation(LatLon::from_dms((35, 2, 0), (135, 1, 30)))
+            .unwrap();
+        assert_abs_diff_eq!(ret, 38.1, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn bilinear() {
+        let ret = asc()
+            .undulation(LatLon::from_dms((35, 0, 30), (135, 0, 45)))
+            .unwrap();
+        assert_abs_diff_eq!(ret, 36.55, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn nodata() {
+        let sut = asc();
+        assert_eq!(
+            sut.undulation(LatLon::from_dms((35, 1, 30), (135, 4, 0))),
+            None
+        );
+        assert!(sut
+            .undulation(LatLon::from_dms((35, 1, 30), (135, 2, 0)))
+            .is_some());
+    }
+
+    #[test]
+    fn outside() {
+        let sut = asc();
+        assert_eq!(sut.undulation(LatLon::new(34.99, 135.)), None);
+        assert_eq!(sut.undulation(LatLon::new(35., 135.1)), None);
+        assert!(sut
+            .undulation(LatLon::from_dms((35, 2, 0), (135, 0, 0)))
+            .is_some());
+    }
+
+    #[test]
+    fn heights() {
+        let sut = asc();
+        let p = LatLon::new(35., 135.);
+        assert_abs_diff_eq!(
+            sut.orthometric_height(p, 100.).unwrap(),
+            64.0,
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(
+            sut.ellipsoidal_height(p, 64.).unwrap(),
+            100.0,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn isg() {
+        let isg = Geoid::from_isg(ISG.as_bytes()).unwrap();
+        let asc = asc();
+        for p in [
+            LatLon::new(35., 135.),
+            LatLon::from_dms((35, 0, 30), (135, 0, 45)),
+            LatLon::from_dms((35, 1, 40), (135, 2, 10)),
+            LatLon::from_dms((35, 1, 30), (135, 4, 0)),
+        ] {
+            assert_eq!(isg.undulation(p), asc.undulation(p));
+        }
+    }
+
+    #[test]
+    fn parse_dms() {
+        assert_abs_diff_eq!(parse_angle("135°04'30\"").unwrap(), 135.075);
+        assert_abs_diff_eq!(parse_angle("-0°30'00\"").unwrap(), -0.5);
+        assert_abs_diff_eq!(parse_angle("0.016667").unwrap(), 0.016667);
+    }
+
+    #[test]
+    fn broken() {
+        assert!(Geoid::from_asc("35 135 0.1 0.1 2 2\n1 2 3\n".as_bytes()).is_err());
+        assert!(Geoid::from_isg("begin_of_head\nnrows : 2\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn isg_missing_header() {
+        for key in [
+            "lat min",
+            "lat max",
+            "lon min",
+            "lon max",
+            "delta lat",
+            "delta lon",
+        ] {
+            let isg: String = ISG
+                .lines()
+                .filter(|l| !l.starts_with(key))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let err = Geoid::from_isg(isg.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(err.to_string(), format!("missing {key}"));
+        }
+    }
+
+    #[test]
+    fn isg_without_nodata() {
+        let isg = ISG
+            .replace("nodata         : -9999.0000\n", "")
+            .replace("-9999.0000", "   37.3000");
+        let sut = Geoid::from_isg(isg.as_bytes()).unwrap();
+        let ret = sut
+            .undulation(LatLon::from_dms((35, 1, 0), (135, 4, 30)))
+            .unwrap();
+        assert_abs_diff_eq!(ret, 37.3, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn isg_nodata() {
+        let sut = Geoid::from_isg(ISG.as_bytes()).unwrap();
+        assert_eq!(
+            sut.undulation(LatLon::from_dms((35, 1, 0), (135, 4, 30))),
+            None
+        );
+        assert_eq!(
+            sut.undulation(LatLon::from_dms((35, 1, 30), (135, 4, 0))),
+            None
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use geo_types::{
+    Coord, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon, Rect, Triangle,
+};
+
+use crate::LatLon;
+
+/// [`geo_types`] の形状を測地系変換する。
+/// Transform geodetic datums of [`geo_types`] geometries.
+///
+/// 座標の `x` を経度、`y` を緯度 (いずれも度) として扱う。
+/// 緯度経度の順序の入れ替えは、内部で行われる。
+///
+/// 各メソッドは、点ごとに対応する測地系の変換メソッドを用いる。
+/// ただし [`Rect`] は、最小と最大の角のみを変換した近似となる。
+///
+/// # Examples
+///
+/// ```
+/// use geo_types::polygon;
+/// use jgd::GeoTransform;
+///
+/// # #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+/// # {
+/// let tokyo_datum = polygon![
+///     (x: 135.0, y: 35.0),
+///     (x: 135.1, y: 35.0),
+///     (x: 135.1, y: 35.1),
+/// ];
+/// let jgd2011 = tokyo_datum.tokyo_to_jgd2011();
+/// # }
+/// ```
+pub trait GeoTransform: Sized {
+    #[doc(hidden)]
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self;
+
+    /// [`Tokyo::to_jgd2000`](crate::Tokyo::to_jgd2000) を用いて変換する。
+    /// Transform from Tokyo Datum to JGD2000.
+    #[cfg(feature = "tky2jgd")]
+    fn tokyo_to_jgd2000(&self) -> Self {
+        self.map_degrees(&|p| crate::Tokyo::new(p).to_jgd2000().degrees())
+    }
+
+    /// [`Tokyo::to_jgd2000`](crate::Tokyo::to_jgd2000) と [`Jgd2000::to_jgd2011`](crate::Jgd2000::to_jgd2011) を用いて変換する。
+    /// Transform from Tokyo Datum to JGD2011.
+    #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+    fn tokyo_to_jgd2011(&self) -> Self {
+        self.map_degrees(&|p| crate::Tokyo::new(p).to_jgd2000().to_jgd2011().degrees())
+    }
+
+    /// [`Tokyo97::to_jgd2000`](crate::Tokyo97::to_jgd2000) を用いて変換する。
+    /// Transform from Tokyo97 to JGD2000.
+    fn tokyo97_to_jgd2000(&self) -> Self {
+        self.map_degrees(&|p| crate::Tokyo97::new(p).to_jgd2000().degrees())
+    }
+
+    /// [`Tokyo97::to_jgd2000`](crate::Tokyo97::to_jgd2000) と [`Jgd2000::to_jgd2011`](crate::Jgd2000::to_jgd2011) を用いて変換する。
+    /// Transform from Tokyo97 to JGD2011.
+    #[cfg(feature = "patchjgd")]
+    fn tokyo97_to_jgd2011(&self) -> Self {
+        self.map_degrees(&|p| crate::Tokyo97::new(p).to_jgd2000().to_jgd2011().degrees())
+    }
+
+    /// [`Jgd2000::to_tokyo`](crate::Jgd2000::to_tokyo) を用いて変換する。
+    /// Transform from JGD2000 to Tokyo Datum.
+    #[cfg(feature = "tky2jgd")]
+    fn jgd2000_to_tokyo(&self) -> Self {
+        self.map_degrees(&|p| crate::Jgd2000::new(p).to_tokyo().degrees())
+    }
+
+    /// [`Jgd2000::to_tokyo97`](crate::Jgd2000::to_tokyo97) を用いて変換する。
+    /// Transform from JGD2000 to Tokyo97.
+    fn jgd2000_to_tokyo97(&self) -> Self {
+        self.map_degrees(&|p| crate::Jgd2000::new(p).to_tokyo97().lat_lon())
+    }
+
+    /// [`Jgd2000::to_jgd2011`](crate::Jgd2000::to_jgd2011) を用いて変換する。
+    /// Transform from JGD2000 to JGD2011.
+    #[cfg(feature = "patchjgd")]
+    fn jgd2000_to_jgd2011(&self) -> Self {
+        self.map_degrees(&|p| crate::Jgd2000::new(p).to_jgd2011().degrees())
+    }
+
+    /// [`Jgd2011::to_jgd2000`](crate::Jgd2011::to_jgd2000) を用いて変換する。
+    /// Transform from JGD2011 to JGD2000.
+    #[cfg(feature = "patchjgd")]
+    fn jgd2011_to_jgd2000(&self) -> Self {
+        self.map_degrees(&|p| crate::Jgd2011::new(p).to_jgd2000().degrees())
+    }
+
+    /// [`Jgd2011::to_jgd2000`](crate::Jgd2011::to_jgd2000) と [`Jgd2000::to_tokyo`](crate::Jgd2000::to_tokyo) を用いて変換する。
+    /// Transform from JGD2011 to Tokyo Datum.
+    #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+    fn jgd2011_to_tokyo(&self) -> Self {
+        self.map_degrees(&|p| crate::Jgd2011::new(p).to_jgd2000().to_tokyo().degrees())
+    }
+
+    /// [`Jgd2011::to_jgd2000`](crate::Jgd2011::to_jgd2000) と [`Jgd2000::to_tokyo97`](crate::Jgd2000::to_tokyo97) を用いて変換する。
+    /// Transform from JGD2011 to Tokyo97.
+    #[cfg(feature = "patchjgd")]
+    fn jgd2011_to_tokyo97(&self) -> Self {
+        self.map_degrees(&|p| crate::Jgd2011::new(p).to_jgd2000().to_tokyo97().lat_lon())
+    }
+}
+impl GeoTransform for Coord {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        // 順序に注意: lat, lon <=> y, x
+        let (y, x) = f(LatLon::new(self.y, self.x)).into();
+        Coord { x, y }
+    }
+}
+impl GeoTransform for Point {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        Point(self.0.map_degrees(f))
+    }
+}
+impl GeoTransform for Line {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        Line::new(self.start.map_degrees(f), self.end.map_degrees(f))
+    }
+}
+impl GeoTransform for LineString {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        self.0.iter().map(|c| c.map_degrees(f)).collect()
+    }
+}
+impl GeoTransform for Polygon {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        let interiors = self.interiors().iter().map(|r| r.map_degrees(f)).collect();
+        Polygon::new(self.exterior().map_degrees(f), interiors)
+    }
+}
+impl GeoTransform for MultiPoint {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        self.0.iter().map(|p| p.map_degrees(f)).collect()
+    }
+}
+impl GeoTransform for MultiLineString {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        self.0.iter().map(|l| l.map_degrees(f)).collect()
+    }
+}
+impl GeoTransform for MultiPolygon {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        self.0.iter().map(|p| p.map_degrees(f)).collect()
+    }
+}
+/// 最小と最大の角のみを変換した、近似の矩形。
+/// Approximation that transforms only the min and max corners.
+///
+/// 変換量は場所によって異なるため、他の2つの角や辺上の点は、変換後の矩形と一致しない。
+/// 正確に変換するには、[`Polygon`] に変換してから変換する。
+impl GeoTransform for Rect {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        Rect::new(self.min().map_degrees(f), self.max().map_degrees(f))
+    }
+}
+impl GeoTransform for Triangle {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        self.to_array().map(|c| c.map_degrees(f)).into()
+    }
+}
+impl GeoTransform for Geometry {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        match self {
+            Geometry::Point(g) => Geometry::Point(g.map_degrees(f)),
+            Geometry::Line(g) => Geometry::Line(g.map_degrees(f)),
+            Geometry::LineString(g) => Geometry::LineString(g.map_degrees(f)),
+            Geometry::Polygon(g) => Geometry::Polygon(g.map_degrees(f)),
+            Geometry::MultiPoint(g) => Geometry::MultiPoint(g.map_degrees(f)),
+            Geometry::MultiLineString(g) => Geometry::MultiLineString(g.map_degrees(f)),
+            Geometry::MultiPolygon(g) => Geometry::MultiPolygon(g.map_degrees(f)),
+            Geometry::GeometryCollection(g) => Geometry::GeometryCollection(g.map_degrees(f)),
+            Geometry::Rect(g) => Geometry::Rect(g.map_degrees(f)),
+            Geometry::Triangle(g) => Geometry::Triangle(g.map_degrees(f)),
+        }
+    }
+}
+impl GeoTransform for GeometryCollection {
+    fn map_degrees(&self, f: &dyn Fn(LatLon) -> LatLon) -> Self {
+        self.0.iter().map(|g| g.map_degrees(f)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::{line_string, point, polygon};
+
+    use super::*;
+
+    fn shift(p: LatLon) -> LatLon {
+        let (lat, lon) = p.into();
+        LatLon::new(lat + 1., lon + 2.)
+    }
+
+    #[test]
+    fn lon_lat_order() {
+        let ret = point!(x: 135., y: 35.).map_degrees(&shift);
+        assert_eq!(ret, point!(x: 137., y: 36.));
+    }
+
+    #[test]
+    fn nested() {
+        let polygon = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)],
+            interiors: [[(x: 0.5, y: 0.2), (x: 0.8, y: 0.2), (x: 0.8, y: 0.5)]],
+        );
+        let collection = GeometryCollection::from(vec![
+            Geometry::from(polygon.clone()),
+            Geometry::from(line_string![(x: 0., y: 0.), (x: 1., y: 1.)]),
+        ]);
+        let ret = collection.map_degrees(&shift);
+        assert_eq!(ret.0.len(), 2);
+        let Geometry::Polygon(ret) = &ret.0[0] else {
+            panic!("must be a polygon");
+        };
+        assert_eq!(ret.exterior().0[1], Coord { x: 3., y: 1. });
+        assert_eq!(ret.interiors()[0].0[0], Coord { x: 2.5, y: 1.2 });
+    }
+
+    #[cfg(feature = "tky2jgd")]
+    #[test]
+    fn same_as_point() {
+        let (lat, lon) = (35., 135.);
+        let exp = crate::from_tokyo(lat, lon).to_jgd2000().degrees();
+        let ret = point!(x: lon, y: lat).tokyo_to_jgd2000();
+        assert_eq!(LatLon::new(ret.y(), ret.x()), exp);
+    }
+}
@@ -1,62 +1,212 @@
-use crate::LatLon;
+use std::{
+    f64::consts::PI,
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    path::Path,
+};
+
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+use std::sync::OnceLock;
+
+use crate::{earth::GRS80, par, Error, LatLon, LatLonHeight};
+
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+use crate::{Interpolation, Method};
 
 /// 日本測地系から世界測地系への座標変換パラメータ。
 ///
 /// たとえ陸地であっても、無人島や、後年に埋め立てられた沿岸部などで、パラメータグリッドが存在しない。
 ///
+/// 初回の探索時に格子点の索引 (約1.2MB) を作成し、以降は定数時間で探索する。
+///
 /// 出典: 国土地理院 [TKY2JGD.par](https://www.gsi.go.jp/sokuchikijun/tky2jgd_download.html) (Ver.2.1.2, 2003年公開) をもとに形式を変換して作成。
 #[cfg(feature = "tky2jgd")]
-pub const TKY2JGD: Grid = crate::par::TKY2JGD.to_grid();
+pub const TKY2JGD: Grid = crate::par::TKY2JGD.to_grid().with_index(tky2jgd_index);
+
+#[cfg(feature = "tky2jgd")]
+fn tky2jgd_index() -> &'static DenseIndex {
+    static INDEX: OnceLock<DenseIndex> = OnceLock::new();
+    INDEX.get_or_init(|| DenseIndex::new(TKY2JGD.dots))
+}
 
 /// 平成23年(2011年)東北地方太平洋沖地震の座標補正パラメータ。
 ///
 /// 3月11日以降に複雑な地殻変動をともなう地震の発生した地域では、パラメータが存在しない。
 ///
+/// 初回の探索時に格子点の索引を作成し、以降は定数時間で探索する。
+///
 /// 出典: 国土地理院 [touhokutaiheiyouoki2011.par](https://www.gsi.go.jp/sokuchikijun/sokuchikijun41012.html) (Ver.4.0.0, 2017年公開) をもとに形式を変換して作成。
 #[cfg(feature = "patchjgd")]
-pub const TOUHOKUTAIHEIYOUOKI2011: Grid = crate::par::TOUHOKUTAIHEIYOUOKI2011.to_grid();
+pub const TOUHOKUTAIHEIYOUOKI2011: Grid = crate::par::TOUHOKUTAIHEIYOUOKI2011
+    .to_grid()
+    .with_index(touhokutaiheiyouoki2011_index);
+
+#[cfg(feature = "patchjgd")]
+fn touhokutaiheiyouoki2011_index() -> &'static DenseIndex {
+    static INDEX: OnceLock<DenseIndex> = OnceLock::new();
+    INDEX.get_or_init(|| DenseIndex::new(TOUHOKUTAIHEIYOUOKI2011.dots))
+}
 
 /// パラメータグリッド。
 /// Parameters grid.
-pub struct Grid<'a> {
-    dots: &'a [Dot],
+///
+/// `S` は格子点ごとのパラメータで、水平方向の補正量 [`MicroSecond`]、標高の補正量 [`MicroMeter`]、または地殻変動量 [`Displacement`] のいずれか。
+pub struct Grid<'a, S = MicroSecond> {
+    dots: &'a [Dot<S>],
+
+    /// 格子の間隔 (3次メッシュ単位)
+    step: i16,
+
+    /// 組み込みのグリッドで、遅延して作成される索引。なければ二分探索する
+    index: Option<fn() -> &'static DenseIndex>,
 }
-impl<'a> Grid<'a> {
-    #[allow(dead_code)]
-    pub(crate) const fn new(dots: &'a [Dot]) -> Self {
-        Self { dots }
+impl<'a, S> Grid<'a, S> {
+    pub(crate) const fn new(dots: &'a [Dot<S>]) -> Self {
+        Self {
+            dots,
+            step: 1,
+            index: None,
+        }
     }
 
-    /// バイリニア補間。
-    /// Bilinear interpolation.
-    ///
-    /// 指定された座標が属する3次メッシュの四隅すべてのパラメータがグリッド内に存在しなければならない。
-    /// 一つでも欠けていた場合は `None` を返す。
-    pub fn bilinear(&self, p: LatLon) -> Option<LatLon> {
+    /// 索引を指定する。`index` は `dots` から作成されていなければならない。
+    #[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+    const fn with_index(mut self, index: fn() -> &'static DenseIndex) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// 格子の間隔を3次メッシュ単位で指定する。2次メッシュでは10。
+    pub(crate) const fn with_step(mut self, step: i16) -> Self {
+        self.step = step;
+        self
+    }
+}
+impl<S: Copy> Grid<'_, S> {
+    /// 指定された座標が属する格子の四隅 (南西, 南東, 北西, 北東) のパラメータと重み
+    fn corners(&self, p: LatLon) -> Option<[(S, f64); 4]> {
+        let mesh = Mesh3::floor(p, self.step);
+        let shifts = self.corner_shifts(mesh)?;
+        Some(self.weighted(mesh, p, shifts))
+    }
+
+    /// [`Grid::corners`] と同じ。直前と同じ格子であれば、探索を省略する。
+    #[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+    fn corners_cached(&self, p: LatLon, cache: &mut Cache<S>) -> Option<[(S, f64); 4]> {
+        let mesh = Mesh3::floor(p, self.step);
+        let shifts = match cache.0 {
+            Some((cached, shifts)) if cached == mesh => shifts,
+            _ => {
+                let shifts = self.corner_shifts(mesh)?;
+                cache.0 = Some((mesh, shifts));
+                shifts
+            }
+        };
+        Some(self.weighted(mesh, p, shifts))
+    }
+
+    /// 南西端が `mesh` の格子の四隅 (南西, 南東, 北西, 北東) のパラメータ
+    fn corner_shifts(&self, mesh: Mesh3) -> Option<[S; 4]> {
         // > 地域毎の変換パラメータの格子点は，3 次メッシュの中央ではなく，南西隅に対応する (飛田, 2001)
-        let mesh = Mesh3::floor(p);
+        let step = self.step;
+        if let Some(index) = self.index {
+            let index = index();
+            let find = |mesh| Some(self.dots[index.find(mesh)?].shift);
+            return Some([
+                find(mesh)?,
+                find(mesh.east(step))?,
+                find(mesh.north(step))?,
+                find(mesh.north(step).east(step))?,
+            ]);
+        }
+
         let i = self.search_after(0, mesh)?;
         let sw_shift = self.dots[i].shift;
 
-        let i = self.search_at(i + 1, mesh.east())?;
+        let i = self.search_at(i + 1, mesh.east(step))?;
         let se_shift = self.dots[i].shift;
 
-        let i = self.search_after(i + 1, mesh.north())?;
+        let i = self.search_after(i + 1, mesh.north(step))?;
         let nw_shift = self.dots[i].shift;
 
-        let i = self.search_at(i + 1, mesh.north().east())?;
+        let i = self.search_at(i + 1, mesh.north(step).east(step))?;
         let ne_shift = self.dots[i].shift;
 
-        let (n_weight, e_weight) = mesh.diagonal_weight(p).into();
-        let (s_weight, w_weight) = mesh.north().east().diagonal_weight(p).into();
+        Some([sw_shift, se_shift, nw_shift, ne_shift])
+    }
 
-        // weighted mean
-        let shift = sw_shift.to_degree() * s_weight * w_weight
-            + se_shift.to_degree() * s_weight * e_weight
-            + nw_shift.to_degree() * n_weight * w_weight
-            + ne_shift.to_degree() * n_weight * e_weight;
+    /// 四隅のパラメータに、`p` に応じた重みを付ける
+    fn weighted(&self, mesh: Mesh3, p: LatLon, shifts: [S; 4]) -> [(S, f64); 4] {
+        let step = self.step;
+        let (n_weight, e_weight) = mesh.diagonal_weight(p, step).into();
+        let ne = mesh.north(step).east(step);
+        let (s_weight, w_weight) = ne.diagonal_weight(p, step).into();
 
-        Some(shift)
+        let [sw_shift, se_shift, nw_shift, ne_shift] = shifts;
+        [
+            (sw_shift, s_weight * w_weight),
+            (se_shift, s_weight * e_weight),
+            (nw_shift, n_weight * w_weight),
+            (ne_shift, n_weight * e_weight),
+        ]
+    }
+
+    /// 四隅 (南西, 南東, 北西, 北東) のうち、存在するパラメータと重み
+    fn partial_corners(&self, p: LatLon) -> [(Option<S>, f64); 4] {
+        let step = self.step;
+        let sw = Mesh3::floor(p, step);
+        let ne = sw.north(step).east(step);
+        let (n_weight, e_weight) = sw.diagonal_weight(p, step).into();
+        let (s_weight, w_weight) = ne.diagonal_weight(p, step).into();
+
+        [
+            (self.find(sw), s_weight * w_weight),
+            (self.find(sw.east(step)), s_weight * e_weight),
+            (self.find(sw.north(step)), n_weight * w_weight),
+            (self.find(ne), n_weight * e_weight),
+        ]
+    }
+
+    /// `p` から `limit` メートル以内で最も近い格子点のパラメータ
+    fn nearest_shift(&self, p: LatLon, limit: f64) -> Option<S> {
+        let meter_per_lat = GRS80.equatorial_radius() * PI / 180.;
+        let meter_per_lon = meter_per_lat * p.lat().to_radians().cos();
+        let lat_range = limit / meter_per_lat;
+        let lon_range = limit / meter_per_lon;
+
+        // "saturating cast" as in Mesh3::floor
+        let south = ((p.lat() - lat_range) * 120.).floor() as i16;
+        let north = ((p.lat() + lat_range) * 120.).ceil() as i16;
+        let west = ((p.lon() - lon_range) * 80.).floor() as i16;
+        let east = ((p.lon() + lon_range) * 80.).ceil() as i16;
+
+        let mut nearest = None;
+        let mut min_distance = limit;
+        for lat in south..=north {
+            let first = self
+                .dots
+                .partition_point(|dot| dot.mesh < Mesh3 { lat, lon: west });
+            for dot in self.dots[first..]
+                .iter()
+                .take_while(|dot| dot.mesh <= Mesh3 { lat, lon: east })
+            {
+                let d = dot.mesh.to_degree() - p;
+                let distance = (d.lat() * meter_per_lat).hypot(d.lon() * meter_per_lon);
+                if distance <= min_distance {
+                    min_distance = distance;
+                    nearest = Some(dot.shift);
+                }
+            }
+        }
+        nearest
+    }
+
+    fn find(&self, query: Mesh3) -> Option<S> {
+        let i = match self.index {
+            Some(index) => index().find(query)?,
+            None => self.search_after(0, query)?,
+        };
+        Some(self.dots[i].shift)
     }
 
     fn search_after(&self, first: usize, query: Mesh3) -> Option<usize> {
@@ -70,19 +220,517 @@ impl<'a> Grid<'a> {
     fn search_at(&self, index: usize, query: Mesh3) -> Option<usize> {
         (self.dots.get(index)?.mesh == query).then_some(index)
     }
+}
+impl Grid<'_> {
+    /// バイリニア補間。
+    /// Bilinear interpolation.
+    ///
+    /// 指定された座標が属する3次メッシュの四隅すべてのパラメータがグリッド内に存在しなければならない。
+    /// 一つでも欠けていた場合は `None` を返す。
+    pub fn bilinear(&self, p: LatLon) -> Option<LatLon> {
+        Some(weighted_mean(self.corners(p)?))
+    }
+
+    /// [`Grid::bilinear`] と同じ。直前と同じ格子であれば、探索を省略する。
+    #[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+    pub(crate) fn bilinear_cached(&self, p: LatLon, cache: &mut Cache) -> Option<LatLon> {
+        Some(weighted_mean(self.corners_cached(p, cache)?))
+    }
+
+    /// バイリニア補間の逆算。
+    /// Inverse of bilinear interpolation.
+    ///
+    /// 補間したパラメータを加えると `p` になる座標を反復計算で求め、そのパラメータを返す。
+    /// 反復の途中で [`Grid::bilinear`] が `None` を返した場合は `None` を返す。
+    ///
+    /// 反復の上限 (16回) までに収束しなかった場合も、エラーとせず、最後に補間したパラメータを `Some` で返す。
+    /// 収束しない場合をエラーとするには、[`Jgd2000::try_to_tokyo`](crate::Jgd2000::try_to_tokyo) などの `try_` で始まる変換メソッドを用いる。
+    pub fn bilinear_inverse(&self, p: LatLon) -> Option<LatLon> {
+        let (shift, _) = iterate_inverse(p, |p| self.bilinear(p), |shift| *shift)?;
+        Some(shift)
+    }
+
+    /// [`Grid::bilinear_inverse`] の収束しない場合をエラーとするもの。
+    pub(crate) fn try_bilinear_inverse(&self, p: LatLon) -> Result<LatLon, Error> {
+        try_inverse(p, iterate_inverse(p, |p| self.bilinear(p), |shift| *shift))
+    }
+
+    /// [`Grid::bilinear_inverse`] と同じ。直前と同じ格子であれば、探索を省略する。
+    #[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+    pub(crate) fn bilinear_inverse_cached(&self, p: LatLon, cache: &mut Cache) -> Option<LatLon> {
+        let bilinear = |p| self.bilinear_cached(p, cache);
+        let (shift, _) = iterate_inverse(p, bilinear, |shift| *shift)?;
+        Some(shift)
+    }
+
+    /// 存在する隅のみによる補間。
+    /// Interpolation from the existing corners.
+    ///
+    /// 指定された座標が属する3次メッシュの四隅のうち、グリッド内に存在するパラメータのみを、バイリニア補間の重みで加重平均する。
+    /// 国土地理院の変換ソフトウェアが、パラメータグリッドの縁で行う補間と同様。
+    ///
+    /// 四隅すべてが存在する場合は [`Grid::bilinear`] と同じ。
+    /// 存在する隅の重みの合計が0の場合 (すべて欠けている場合を含む) は `None` を返す。
+    pub fn bilinear_partial(&self, p: LatLon) -> Option<LatLon> {
+        let (shift, total) = self
+            .partial_corners(p)
+            .into_iter()
+            .filter_map(|(shift, weight)| Some((shift?.to_degree() * weight, weight)))
+            .fold((LatLon::default(), 0.), |(sum, total), (x, weight)| {
+                (sum + x, total + weight)
+            });
+
+        (total > 0.).then(|| shift * total.recip())
+    }
+
+    /// 最近傍補間。
+    /// Nearest-neighbor interpolation.
+    ///
+    /// `p` から `limit` メートル以内で、最も近い格子点のパラメータを返す。
+    /// 距離は、地球を赤道半径の球とみなした近似値。
+    /// 範囲内に格子点が存在しない場合は `None` を返す。
+    pub fn nearest(&self, p: LatLon, limit: f64) -> Option<LatLon> {
+        self.nearest_shift(p, limit).map(MicroSecond::to_degree)
+    }
+
+    /// 指定された方法で補間し、実際に用いた方法とともに返す。
+    ///
+    /// 四隅すべてが存在すれば、いずれの方法でも [`Method::Bilinear`] となる。
+    #[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+    pub(crate) fn interpolate(
+        &self,
+        p: LatLon,
+        interpolation: Interpolation,
+    ) -> Option<(LatLon, Method)> {
+        if let Some(shift) = self.bilinear(p) {
+            return Some((shift, Method::Bilinear));
+        }
+        match interpolation {
+            Interpolation::Bilinear => None,
+            Interpolation::Partial => Some((self.bilinear_partial(p)?, Method::Partial)),
+            Interpolation::Nearest { limit } => Some((self.nearest(p, limit)?, Method::Nearest)),
+        }
+    }
+}
+impl Grid<'_, MicroMeter> {
+    /// バイリニア補間。
+    /// Bilinear interpolation.
+    ///
+    /// 標高の補正量 (メートル) を返す。
+    /// 四隅のパラメータが一つでも欠けていた場合は `None` を返す。
+    pub fn bilinear(&self, p: LatLon) -> Option<f64> {
+        let shift = self
+            .corners(p)?
+            .into_iter()
+            .map(|(shift, weight)| shift.to_meter() * weight)
+            .sum();
+
+        Some(shift)
+    }
+}
+impl Grid<'_, Displacement> {
+    /// バイリニア補間。
+    /// Bilinear interpolation.
+    ///
+    /// 緯度経度 (度) と高さ (メートル) の地殻変動量を返す。
+    /// 四隅のパラメータが一つでも欠けていた場合は `None` を返す。
+    pub fn bilinear(&self, p: LatLon) -> Option<LatLonHeight> {
+        let (lat_lon, height) = self.corners(p)?.into_iter().fold(
+            (LatLon::default(), 0.),
+            |(lat_lon, height), (shift, weight)| {
+                let (d_lat_lon, d_height) = shift.to_degree_meter();
+                (lat_lon + d_lat_lon * weight, height + d_height * weight)
+            },
+        );
+
+        Some(LatLonHeight::new(lat_lon, height))
+    }
+
+    /// バイリニア補間の逆算。
+    /// Inverse of bilinear interpolation.
+    ///
+    /// 補間した水平方向の地殻変動量を加えると `p` になる座標を反復計算で求め、その地殻変動量を返す。
+    ///
+    /// [`Grid::bilinear_inverse`] と同じく、反復の上限までに収束しなかった場合も、最後に補間した値を `Some` で返す。
+    pub fn bilinear_inverse(&self, p: LatLon) -> Option<LatLonHeight> {
+        let (shift, _) = iterate_inverse(p, |p| self.bilinear(p), LatLonHeight::lat_lon)?;
+        Some(shift)
+    }
+
+    /// [`Grid::bilinear_inverse`] の収束しない場合をエラーとするもの。
+    pub(crate) fn try_bilinear_inverse(&self, p: LatLon) -> Result<LatLonHeight, Error> {
+        try_inverse(
+            p,
+            iterate_inverse(p, |p| self.bilinear(p), LatLonHeight::lat_lon),
+        )
+    }
+}
+
+/// バイリニア補間で、直前に探索した格子。
+///
+/// 近い座標を連続して補間する場合に、同じ格子の探索を省略する。
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+pub(crate) struct Cache<S = MicroSecond>(Option<(Mesh3, [S; 4])>);
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+impl<S> Default for Cache<S> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+/// パラメータの加重平均
+fn weighted_mean(corners: [(MicroSecond, f64); 4]) -> LatLon {
+    corners
+        .into_iter()
+        .map(|(shift, weight)| shift.to_degree() * weight)
+        .fold(LatLon::default(), |sum, x| sum + x)
+}
+
+/// 逆算の収束判定: 1e-10度 ≒ 0.01mm
+const TOLERANCE: f64 = 1e-10;
+
+/// 逆算の反復回数の上限
+const MAX_ITERATIONS: usize = 16;
+
+/// 補間したパラメータの水平成分を加えると `p` になる座標を反復計算で求め、そのパラメータと、収束したかどうかを返す。
+///
+/// 収束しなかった場合は、最後に補間したパラメータを返す。
+fn iterate_inverse<T>(
+    p: LatLon,
+    mut bilinear: impl FnMut(LatLon) -> Option<T>,
+    horizontal: impl Fn(&T) -> LatLon,
+) -> Option<(T, bool)> {
+    let mut shift = bilinear(p)?;
+    for _ in 0..MAX_ITERATIONS {
+        let next = bilinear(p - horizontal(&shift))?;
+        let residual = horizontal(&next) - horizontal(&shift);
+        shift = next;
+        if residual.lat().abs() < TOLERANCE && residual.lon().abs() < TOLERANCE {
+            return Some((shift, true));
+        }
+    }
+    Some((shift, false))
+}
+
+/// [`iterate_inverse`] の結果をエラーに変換する。
+fn try_inverse<T>(p: LatLon, inverse: Option<(T, bool)>) -> Result<T, Error> {
+    match inverse {
+        Some((shift, true)) => Ok(shift),
+        Some((_, false)) => Err(Error::NotConverged(p)),
+        None => Err(Error::OutsideGrid(p)),
+    }
+}
+
+/// 実行時に読み込んだパラメータグリッド。
+/// Owned parameters grid loaded at runtime.
+///
+/// 国土地理院の `.par` ファイル、または `par/conv` で変換した `.in` ファイルから読み込む。
+/// 補間は [`Grid`] と同じ。
+///
+/// `S` は [`Grid`] と同じく、格子点ごとのパラメータの種類。
+///
+/// # Examples
+///
+/// ```no_run
+/// use jgd::GridBuf;
+///
+/// let grid: GridBuf = GridBuf::open("kumamoto2016.par")?;
+/// let p = jgd::from_jgd2011(32.8, 130.7).degrees();
+/// let shift = grid.bilinear(p).unwrap_or_default();
+/// let (lat, lon) = jgd::Jgd2011::new(p + shift).into();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridBuf<S = MicroSecond> {
+    dots: Vec<Dot<S>>,
+
+    /// 格子の間隔 (3次メッシュ単位)
+    step: i16,
+}
+impl<S: Parameter> GridBuf<S> {
+    /// ファイルから読み込む。
+    /// Loads from a file.
+    ///
+    /// 拡張子が `.in` であれば [`GridBuf::from_in`]、それ以外は [`GridBuf::from_par`] として読み込む。
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let reader = BufReader::new(File::open(path)?);
+        match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("in") => Self::from_in(reader),
+            _ => Self::from_par(reader),
+        }
+    }
+
+    /// 国土地理院の `.par` 形式から読み込む。
+    /// Parses the `.par` format of GSI.
+    ///
+    /// `MeshCode` で始まるヘッダー行の列名が、パラメータの種類と一致しなければならない。
+    pub fn from_par(reader: impl Read) -> io::Result<Self> {
+        par::parse(reader).map(Self::new)
+    }
+
+    /// `par/conv` で変換した `.in` 形式から読み込む。
+    /// Reads the compact binary `.in` format converted by `par/conv`.
+    pub fn from_in(reader: impl Read) -> io::Result<Self> {
+        par::read(reader).map(|dots| Self { dots, step: 1 })
+    }
+
+    /// `.in` 形式で書き出す。
+    /// Writes in the compact binary `.in` format.
+    pub fn write_in(&self, writer: impl Write) -> io::Result<()> {
+        par::write(&self.dots, writer)
+    }
+
+    /// 借用した [`Grid`] に変換する。
+    /// Borrows as [`Grid`].
+    pub fn as_grid(&self) -> Grid<'_, S> {
+        Grid::new(&self.dots).with_step(self.step)
+    }
+
+    /// 格子点の数。
+    /// Number of grid points.
+    pub fn len(&self) -> usize {
+        self.dots.len()
+    }
+
+    /// 格子点がない場合は `true`。
+    /// Returns `true` if there are no grid points.
+    pub fn is_empty(&self) -> bool {
+        self.dots.is_empty()
+    }
+
+    /// 格子点を並べ替える。同じ格子点が複数ある場合は、先頭を残す。
+    fn new(mut dots: Vec<Dot<S>>) -> Self {
+        // 国土地理院のファイルは整列されているとは限らない (TKY2JGD.par の378632行目以降など)
+        dots.sort_by_key(|dot| dot.mesh);
+        dots.dedup_by_key(|dot| dot.mesh);
+        Self { dots, step: 1 }
+    }
+
+    /// 格子の間隔を3次メッシュ単位で指定する。
+    ///
+    /// すべての格子点が間隔の倍数でなければ `None` を返す。
+    pub(crate) fn with_step(mut self, step: i16) -> Option<Self> {
+        let aligned = self.dots.iter().all(|dot| {
+            let (lat, lon) = dot.mesh();
+            lat % step == 0 && lon % step == 0
+        });
+        self.step = step;
+        aligned.then_some(self)
+    }
+}
+impl GridBuf {
+    /// バイリニア補間。
+    /// Bilinear interpolation.
+    ///
+    /// See [`Grid::bilinear`].
+    pub fn bilinear(&self, p: LatLon) -> Option<LatLon> {
+        self.as_grid().bilinear(p)
+    }
+
+    /// バイリニア補間の逆算。
+    /// Inverse of bilinear interpolation.
+    ///
+    /// See [`Grid::bilinear_inverse`].
+    pub fn bilinear_inverse(&self, p: LatLon) -> Option<LatLon> {
+        self.as_grid().bilinear_inverse(p)
+    }
+
+    /// 存在する隅のみによる補間。
+    /// Interpolation from the existing corners.
+    ///
+    /// See [`Grid::bilinear_partial`].
+    pub fn bilinear_partial(&self, p: LatLon) -> Option<LatLon> {
+        self.as_grid().bilinear_partial(p)
+    }
 
     /// 最近傍補間。
     /// Nearest-neighbor interpolation.
-    fn _nearest(&self, _degrees: LatLon, _limit: f64) -> LatLon {
-        todo!()
+    ///
+    /// See [`Grid::nearest`].
+    pub fn nearest(&self, p: LatLon, limit: f64) -> Option<LatLon> {
+        self.as_grid().nearest(p, limit)
+    }
+}
+impl GridBuf<MicroMeter> {
+    /// バイリニア補間。
+    /// Bilinear interpolation.
+    ///
+    /// 標高の補正量 (メートル) を返す。
+    pub fn bilinear(&self, p: LatLon) -> Option<f64> {
+        self.as_grid().bilinear(p)
+    }
+}
+
+/// 索引付きのパラメータグリッド。
+/// Parameters grid with a dense index.
+///
+/// [`Grid::indexed`] で作成する。
+/// [`GridBuf`] から借用した [`Grid`] は格子点を二分探索するが、これは索引により定数時間で探索する。
+/// その代わり、索引のためのメモリを使う。[`TKY2JGD`] では約1.2MB。
+/// 組み込みのグリッドは、初回の探索時に同じ索引を作成するため、作成し直す必要はない。
+///
+/// 補間の結果は [`Grid`] と同じ。
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "tky2jgd")]
+/// # {
+/// use jgd::TKY2JGD;
+///
+/// let grid = TKY2JGD.indexed();
+/// let p = jgd::from_tokyo(35.0, 135.0).degrees();
+/// assert_eq!(grid.bilinear(p), TKY2JGD.bilinear(p));
+/// # }
+/// ```
+pub struct IndexedGrid<'a, S = MicroSecond> {
+    grid: Grid<'a, S>,
+    index: DenseIndex,
+}
+impl<'a, S: Copy> Grid<'a, S> {
+    /// 格子点の索引を作成する。
+    /// Builds a dense index of grid points.
+    pub fn indexed(&self) -> IndexedGrid<'a, S> {
+        IndexedGrid {
+            grid: Grid::new(self.dots).with_step(self.step),
+            index: DenseIndex::new(self.dots),
+        }
     }
 }
+impl<S: Copy> IndexedGrid<'_, S> {
+    /// [`Grid::corners`] と同じ。
+    fn corners(&self, p: LatLon) -> Option<[(S, f64); 4]> {
+        let step = self.grid.step;
+        let mesh = Mesh3::floor(p, step);
+        let find = |mesh| Some(self.grid.dots[self.index.find(mesh)?].shift);
+        let shifts = [
+            find(mesh)?,
+            find(mesh.east(step))?,
+            find(mesh.north(step))?,
+            find(mesh.north(step).east(step))?,
+        ];
+        Some(self.grid.weighted(mesh, p, shifts))
+    }
+}
+impl IndexedGrid<'_> {
+    /// バイリニア補間。
+    /// Bilinear interpolation.
+    ///
+    /// See [`Grid::bilinear`].
+    pub fn bilinear(&self, p: LatLon) -> Option<LatLon> {
+        Some(weighted_mean(self.corners(p)?))
+    }
+
+    /// バイリニア補間の逆算。
+    /// Inverse of bilinear interpolation.
+    ///
+    /// See [`Grid::bilinear_inverse`].
+    pub fn bilinear_inverse(&self, p: LatLon) -> Option<LatLon> {
+        let (shift, _) = iterate_inverse(p, |p| self.bilinear(p), |shift| *shift)?;
+        Some(shift)
+    }
+}
+
+/// 緯度ごとに、経度の範囲で密な、格子点の索引
+struct DenseIndex {
+    lat_min: i16,
+    rows: Vec<Row>,
+
+    /// 各行の経度ごとの、行内の格子点の番号。格子点がなければ [`DenseIndex::NONE`]
+    columns: Vec<u16>,
+}
+struct Row {
+    /// 行の最初の格子点の番号
+    first_dot: usize,
+
+    /// 行の最初の列の番号
+    first_column: usize,
+
+    lon_min: i16,
+    width: usize,
+}
+impl DenseIndex {
+    const NONE: u16 = u16::MAX;
+
+    /// `dots` は緯度、経度の順に整列していなければならない
+    fn new<S>(dots: &[Dot<S>]) -> Self {
+        let lat_min = dots.first().map_or(0, |dot| dot.mesh.lat);
+        let mut rows = Vec::new();
+        let mut columns = Vec::new();
+
+        let mut first_dot = 0;
+        for row in dots.chunk_by(|a, b| a.mesh.lat == b.mesh.lat) {
+            let lat = row[0].mesh.lat;
+            // 格子点のない緯度は、幅0の行とする
+            while lat_min + rows.len() as i16 != lat {
+                rows.push(Row {
+                    first_dot,
+                    first_column: columns.len(),
+                    lon_min: 0,
+                    width: 0,
+                });
+            }
+
+            let lon_min = row[0].mesh.lon;
+            let width = usize::from(row[row.len() - 1].mesh.lon.abs_diff(lon_min)) + 1;
+            let first_column = columns.len();
+            columns.resize(first_column + width, Self::NONE);
+            for (i, dot) in row.iter().enumerate() {
+                // 1行の格子点は i16 の範囲の経度の数より少ない
+                columns[first_column + usize::from(dot.mesh.lon.abs_diff(lon_min))] = i as u16;
+            }
+            rows.push(Row {
+                first_dot,
+                first_column,
+                lon_min,
+                width,
+            });
+            first_dot += row.len();
+        }
+
+        Self {
+            lat_min,
+            rows,
+            columns,
+        }
+    }
+
+    /// 格子点の番号
+    fn find(&self, mesh: Mesh3) -> Option<usize> {
+        let row = self.rows.get(offset(self.lat_min, mesh.lat)?)?;
+        let column = offset(row.lon_min, mesh.lon).filter(|&c| c < row.width)?;
+        match self.columns[row.first_column + column] {
+            Self::NONE => None,
+            i => Some(row.first_dot + usize::from(i)),
+        }
+    }
+}
+
+/// `min` から `x` までの距離。`x` が `min` より小さければ `None`
+fn offset(min: i16, x: i16) -> Option<usize> {
+    (min <= x).then(|| usize::from(x.abs_diff(min)))
+}
 
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
-pub struct Dot {
+pub struct Dot<S = MicroSecond> {
     mesh: Mesh3,
-    shift: MicroSecond,
+    shift: S,
+}
+impl<S> Dot<S> {
+    pub(crate) fn new(lat: i16, lon: i16, shift: S) -> Self {
+        let mesh = Mesh3 { lat, lon };
+        Self { mesh, shift }
+    }
+
+    pub(crate) fn mesh(&self) -> (i16, i16) {
+        (self.mesh.lat, self.mesh.lon)
+    }
+
+    pub(crate) fn shift(&self) -> &S {
+        &self.shift
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -96,26 +744,30 @@ impl Mesh3 {
     const LAT_SEC: f64 = 30.;
     const LON_SEC: f64 = 45.;
 
-    /// Evaluate the southwest of the mesh containing `p`.
-    fn floor(degree: LatLon) -> Self {
+    /// Evaluate the southwest of the grid cell containing `p`, whose size is `step` meshes.
+    fn floor(degree: LatLon, step: i16) -> Self {
         // "saturating cast" since Rust 1.45.0
         // https://blog.rust-lang.org/2020/07/16/Rust-1.45.0.html#fixing-unsoundness-in-casts
         let lat = (degree.lat() * 120.) as i16;
         let lon = (degree.lon() * 80.) as i16;
-        Self { lat, lon }
+        Self {
+            lat: lat - lat.rem_euclid(step),
+            lon: lon - lon.rem_euclid(step),
+        }
     }
-    fn diagonal_weight(self, p: LatLon) -> LatLon {
+    fn diagonal_weight(self, p: LatLon, step: i16) -> LatLon {
         let min_degree = self.to_degree();
-        let weight_lat = (p.lat() - min_degree.lat()).abs() * 3_600. / Self::LAT_SEC;
-        let weight_lon = (p.lon() - min_degree.lon()).abs() * 3_600. / Self::LON_SEC;
+        let step = f64::from(step);
+        let weight_lat = (p.lat() - min_degree.lat()).abs() * 3_600. / (Self::LAT_SEC * step);
+        let weight_lon = (p.lon() - min_degree.lon()).abs() * 3_600. / (Self::LON_SEC * step);
         LatLon::new(weight_lat, weight_lon)
     }
-    fn north(mut self) -> Self {
-        self.lat += 1;
+    fn north(mut self, step: i16) -> Self {
+        self.lat += step;
         self
     }
-    fn east(mut self) -> Self {
-        self.lon += 1;
+    fn east(mut self, step: i16) -> Self {
+        self.lon += step;
         self
     }
     fn to_degree(self) -> LatLon {
@@ -125,12 +777,71 @@ impl Mesh3 {
     }
 }
 
+/// 格子点ごとのパラメータ。
+/// Parameters of each grid point.
+///
+/// `.par` 形式の列と、`.in` 形式の値を対応づける。
+pub trait Parameter: Copy {
+    /// `.par` 形式の列名
+    #[doc(hidden)]
+    const COLUMNS: &'static [&'static str];
+
+    /// `.par` 形式の値から変換する。範囲外の値は `None`
+    #[doc(hidden)]
+    fn from_par(values: &[f64]) -> Option<Self>;
+
+    /// `.in` 形式のマイクロ単位の整数値から変換する
+    #[doc(hidden)]
+    fn from_micros(micros: &[i32]) -> Self;
+
+    /// `.in` 形式のマイクロ単位の整数値に変換する
+    #[doc(hidden)]
+    fn to_micros(self) -> Vec<i32>;
+}
+
+/// `.par` 形式の値を、マイクロ単位の整数値に切り捨てる。
+///
+/// 組み込みのパラメータグリッドと同じく変換するため、[`MicroSecond`] のみに用いる。
+fn to_micro_truncated(value: f64) -> Option<i32> {
+    let micro = value * 1_000_000.;
+    (micro.abs() < f64::from(i32::MAX)).then_some(micro as i32)
+}
+
+/// `.par` 形式の値を、マイクロ単位の整数値に丸める。
+///
+/// `0.0157` が `15699` となるような、浮動小数点数の誤差による切り捨てを避ける。
+fn to_micro(value: f64) -> Option<i32> {
+    to_micro_truncated(value).map(|_| (value * 1_000_000.).round() as i32)
+}
+
+/// 緯度と経度の補正量 (マイクロ秒)。
+/// Shift of latitude and longitude in microseconds.
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
 pub struct MicroSecond {
     lat: i32,
     lon: i32,
 }
+impl Parameter for MicroSecond {
+    const COLUMNS: &'static [&'static str] = &["dB(sec)", "dL(sec)"];
+
+    fn from_par(values: &[f64]) -> Option<Self> {
+        let lat = to_micro_truncated(values[0])?;
+        let lon = to_micro_truncated(values[1])?;
+        Some(Self { lat, lon })
+    }
+
+    fn from_micros(micros: &[i32]) -> Self {
+        Self {
+            lat: micros[0],
+            lon: micros[1],
+        }
+    }
+
+    fn to_micros(self) -> Vec<i32> {
+        vec![self.lat, self.lon]
+    }
+}
 impl MicroSecond {
     fn to_degree(self) -> LatLon {
         let lat = f64::from(self.lat) / 3_600_000_000.;
@@ -139,9 +850,84 @@ impl MicroSecond {
     }
 }
 
+/// 標高の補正量 (マイクロメートル)。
+/// Shift of height in micrometers.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct MicroMeter {
+    height: i32,
+}
+impl Parameter for MicroMeter {
+    const COLUMNS: &'static [&'static str] = &["dH(m)"];
+
+    fn from_par(values: &[f64]) -> Option<Self> {
+        let height = to_micro(values[0])?;
+        Some(Self { height })
+    }
+
+    fn from_micros(micros: &[i32]) -> Self {
+        Self { height: micros[0] }
+    }
+
+    fn to_micros(self) -> Vec<i32> {
+        vec![self.height]
+    }
+}
+impl MicroMeter {
+    fn to_meter(self) -> f64 {
+        f64::from(self.height) / 1_000_000.
+    }
+}
+
+/// 緯度と経度 (マイクロ秒) および高さ (マイクロメートル) の地殻変動量。
+/// Crustal displacement of latitude and longitude in microseconds, and height in micrometers.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Displacement {
+    lat: i32,
+    lon: i32,
+    height: i32,
+}
+impl Parameter for Displacement {
+    const COLUMNS: &'static [&'static str] = &["dB(sec)", "dL(sec)", "dH(m)"];
+
+    fn from_par(values: &[f64]) -> Option<Self> {
+        let lat = to_micro(values[0])?;
+        let lon = to_micro(values[1])?;
+        let height = to_micro(values[2])?;
+        Some(Self { lat, lon, height })
+    }
+
+    fn from_micros(micros: &[i32]) -> Self {
+        Self {
+            lat: micros[0],
+            lon: micros[1],
+            height: micros[2],
+        }
+    }
+
+    fn to_micros(self) -> Vec<i32> {
+        vec![self.lat, self.lon, self.height]
+    }
+}
+impl Displacement {
+    fn to_degree_meter(self) -> (LatLon, f64) {
+        let lat_lon = MicroSecond {
+            lat: self.lat,
+            lon: self.lon,
+        }
+        .to_degree();
+        let height = MicroMeter {
+            height: self.height,
+        }
+        .to_meter();
+        (lat_lon, height)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use approx::assert_ulps_eq;
+    use approx::{assert_abs_diff_eq, assert_ulps_eq};
 
     use super::*;
 
@@ -183,7 +969,7 @@ mod tests {
 
     #[test]
     fn interpolate_corner() {
-        let sut = Grid::new(&SMALLEST);
+        let sut = Grid::new(SMALLEST);
         let ret = sut.bilinear(LatLon::new(0.0, 0.0)).unwrap();
         assert_eq!(ret.lon(), 0.0);
         assert_eq!(ret.lat(), -6. / 3_600_000_000.);
@@ -191,10 +977,264 @@ mod tests {
 
     #[test]
     fn interpolate_middle() {
-        let sut = Grid::new(&SMALLEST);
+        let sut = Grid::new(SMALLEST);
         let exp = LatLon::from_micro_secs(-2, 2);
         let ret = sut.bilinear(LatLon::from_secs(10., 15.)).unwrap();
         assert_ulps_eq!(exp.lat(), ret.lat());
         assert_ulps_eq!(exp.lon(), ret.lon());
     }
+
+    //         45"
+    //  (0) -- (6)
+    //   |      | 30"
+    // (-6) -- (0)
+    const SMALLEST_HEIGHT: &[Dot<MicroMeter>] = &[
+        Dot {
+            mesh: Mesh3 { lon: 0, lat: 0 },
+            shift: MicroMeter { height: -6 },
+        },
+        Dot {
+            mesh: Mesh3 { lon: 1, lat: 0 },
+            shift: MicroMeter { height: 0 },
+        },
+        Dot {
+            mesh: Mesh3 { lon: 0, lat: 1 },
+            shift: MicroMeter { height: 0 },
+        },
+        Dot {
+            mesh: Mesh3 { lon: 1, lat: 1 },
+            shift: MicroMeter { height: 6 },
+        },
+    ];
+
+    #[test]
+    fn interpolate_height() {
+        let sut = Grid::new(SMALLEST_HEIGHT);
+        let ret = sut.bilinear(LatLon::from_secs(10., 15.)).unwrap();
+        assert_ulps_eq!(ret, -2. / 1_000_000.);
+        assert_eq!(sut.bilinear(LatLon::from_secs(40., 15.)), None);
+    }
+
+    /// [`SMALLEST`] を秒単位にして、東京付近に置いたもの
+    const SMALLEST_PAR: &str = "\
+JGD2000 TKY2JGD Ver.2.1.1\r
+MeshCode   dB(sec)   dL(sec)\r
+53394511   6.00000   6.00000\r
+53394500  -6.00000   0.00000\r
+53394501   0.00000   6.00000\r
+53394510   0.00000   0.00000\r
+";
+
+    #[test]
+    fn grid_buf_from_par() {
+        let sut: GridBuf = GridBuf::from_par(SMALLEST_PAR.as_bytes()).unwrap();
+        assert_eq!(sut.len(), 4);
+        // 南西端: 4280 * 30", 11170 * 45"
+        let p = LatLon::from_secs(128_410., 502_665.);
+        let ret = sut.bilinear(p).unwrap();
+        let exp = LatLon::from_secs(-2., 2.);
+        assert_abs_diff_eq!(exp.lat(), ret.lat(), epsilon = 1e-12);
+        assert_abs_diff_eq!(exp.lon(), ret.lon(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn grid_buf_from_par_columns() {
+        // 10文字幅の列を埋める値と、幅の揃っていない値
+        let par = "\
+MeshCode   dB(sec)   dL(sec)\r
+53394500-100.00000-200.00000\r
+53394501 1.5 -2.5\r
+";
+        let dots = par::parse::<MicroSecond>(par.as_bytes()).unwrap();
+        assert_eq!(dots[0].shift().to_micros(), [-100_000_000, -200_000_000]);
+        assert_eq!(dots[1].shift().to_micros(), [1_500_000, -2_500_000]);
+    }
+
+    #[test]
+    fn micro_rounding() {
+        // 組み込みのパラメータグリッドと同じく、秒は切り捨てる
+        let values = [1.9e-6, -1.9e-6, 2.4e-6];
+        assert_eq!(MicroSecond::from_par(&values).unwrap().to_micros(), [1, -1]);
+        // それ以外は丸める
+        assert_eq!(MicroMeter::from_par(&values).unwrap().to_micros(), [2]);
+        assert_eq!(
+            Displacement::from_par(&values).unwrap().to_micros(),
+            [2, -2, 2]
+        );
+        assert!(MicroMeter::from_par(&[3_000.]).is_none());
+
+        // 0.0157 * 1e6 = 15699.999999999998
+        assert_eq!(
+            MicroMeter::from_par(&[0.0157]).unwrap().to_micros(),
+            [15700]
+        );
+        assert_eq!(
+            Displacement::from_par(&[0.0157, -0.0157, 0.0157])
+                .unwrap()
+                .to_micros(),
+            [15700, -15700, 15700]
+        );
+    }
+
+    #[test]
+    fn grid_buf_in() {
+        let par: GridBuf = GridBuf::from_par(SMALLEST_PAR.as_bytes()).unwrap();
+        let mut bytes = Vec::new();
+        par.write_in(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 4 * 12);
+
+        let sut: GridBuf = GridBuf::from_in(&bytes[..]).unwrap();
+        let p = LatLon::from_secs(128_410., 502_665.);
+        assert_eq!(sut.bilinear(p), par.bilinear(p));
+    }
+
+    #[test]
+    fn grid_buf_invalid() {
+        let wrong_columns = GridBuf::<MicroMeter>::from_par(SMALLEST_PAR.as_bytes());
+        assert!(wrong_columns.is_err());
+        let unsorted = [SMALLEST.to_vec(), SMALLEST.to_vec()].concat();
+        let mut bytes = Vec::new();
+        par::write(&unsorted, &mut bytes).unwrap();
+        assert!(GridBuf::<MicroSecond>::from_in(&bytes[..]).is_err());
+        assert!(GridBuf::<MicroSecond>::from_in(&bytes[1..]).is_err());
+    }
+
+    #[cfg(feature = "patchjgd")]
+    #[test]
+    fn grid_buf_same_as_embedded() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/par/touhokutaiheiyouoki2011.in"
+        );
+        let sut: GridBuf = GridBuf::open(path).unwrap();
+        assert_eq!(sut.len(), TOUHOKUTAIHEIYOUOKI2011.dots.len());
+        let p = LatLon::new(38.26, 140.87);
+        assert_eq!(sut.bilinear(p), TOUHOKUTAIHEIYOUOKI2011.bilinear(p));
+    }
+
+    #[test]
+    fn interpolate_partial() {
+        let sut = Grid::new(SMALLEST);
+        let p = LatLon::from_secs(10., 15.);
+        assert_eq!(sut.bilinear_partial(p), sut.bilinear(p));
+
+        // 北東の隅が欠けている
+        let sut = Grid::new(&SMALLEST[..3]);
+        assert_eq!(sut.bilinear(p), None);
+        // 重み: 南西 4/9, 南東 2/9, 北西 2/9
+        let exp = LatLon::from_micro_secs(-3., 1.5);
+        let ret = sut.bilinear_partial(p).unwrap();
+        assert_ulps_eq!(exp.lat(), ret.lat());
+        assert_ulps_eq!(exp.lon(), ret.lon());
+
+        assert_eq!(sut.bilinear_partial(LatLon::from_secs(100., 100.)), None);
+    }
+
+    #[test]
+    fn interpolate_nearest() {
+        let sut = Grid::new(SMALLEST);
+        let inside = LatLon::from_secs(10., 15.);
+        assert_eq!(sut.nearest(inside, 1.), None);
+        assert_eq!(
+            sut.nearest(inside, 1000.),
+            Some(SMALLEST[0].shift.to_degree())
+        );
+
+        // 北東の格子点から北へ30秒 (約930m)、東へ5秒 (約150m)
+        let outside = LatLon::from_secs(60., 50.);
+        assert_eq!(
+            sut.nearest(outside, 1000.),
+            Some(SMALLEST[3].shift.to_degree())
+        );
+        assert_eq!(sut.nearest(outside, 900.), None);
+    }
+
+    /// 緯度の補正量の勾配が1で、逆算が振動する
+    const OSCILLATING: &[Dot] = &[
+        Dot {
+            mesh: Mesh3 { lon: 0, lat: 0 },
+            shift: MicroSecond { lon: 0, lat: 0 },
+        },
+        Dot {
+            mesh: Mesh3 { lon: 1, lat: 0 },
+            shift: MicroSecond { lon: 0, lat: 0 },
+        },
+        Dot {
+            mesh: Mesh3 { lon: 0, lat: 1 },
+            shift: MicroSecond {
+                lon: 0,
+                lat: 30_000_000,
+            },
+        },
+        Dot {
+            mesh: Mesh3 { lon: 1, lat: 1 },
+            shift: MicroSecond {
+                lon: 0,
+                lat: 30_000_000,
+            },
+        },
+    ];
+
+    #[test]
+    fn interpolate_inverse_not_converged() {
+        let sut = Grid::new(OSCILLATING);
+        let p = LatLon::from_secs(10., 15.);
+        assert!(sut.bilinear_inverse(p).is_some());
+        assert_eq!(sut.try_bilinear_inverse(p), Err(Error::NotConverged(p)));
+
+        let outside = LatLon::from_secs(100., 15.);
+        assert_eq!(
+            sut.try_bilinear_inverse(outside),
+            Err(Error::OutsideGrid(outside))
+        );
+    }
+
+    #[test]
+    fn interpolate_inverse() {
+        let sut = Grid::new(SMALLEST);
+        let p = LatLon::from_secs(10., 15.);
+        let shift = sut.bilinear_inverse(p).unwrap();
+        let ret = sut.bilinear(p - shift).unwrap();
+        assert_ulps_eq!(shift.lat(), ret.lat());
+        assert_ulps_eq!(shift.lon(), ret.lon());
+    }
+
+    #[test]
+    fn indexed() {
+        let grid = Grid::new(SMALLEST);
+        let sut = grid.indexed();
+        for (lat, lon) in [(0., 0.), (10., 15.), (30., 45.), (-1., 15.), (10., 46.)] {
+            let p = LatLon::from_secs(lat, lon);
+            assert_eq!(sut.bilinear(p), grid.bilinear(p));
+            assert_eq!(sut.bilinear_inverse(p), grid.bilinear_inverse(p));
+        }
+    }
+
+    #[cfg(feature = "tky2jgd")]
+    #[test]
+    fn indexed_tky2jgd() {
+        let sut = TKY2JGD.indexed();
+        for dot in TKY2JGD.dots.iter().step_by(97) {
+            let p = dot.mesh.to_degree() + LatLon::from_secs(7., 11.);
+            assert_eq!(sut.bilinear(p), TKY2JGD.bilinear(p));
+        }
+        // 範囲外
+        for p in [LatLon::new(20., 120.), LatLon::new(36.31, 143.)] {
+            assert_eq!(sut.bilinear(p), None);
+        }
+    }
+
+    #[cfg(feature = "tky2jgd")]
+    #[test]
+    fn lazy_index_tky2jgd() {
+        let unindexed = Grid::new(TKY2JGD.dots);
+        for dot in TKY2JGD.dots.iter().step_by(97) {
+            let p = dot.mesh.to_degree() + LatLon::from_secs(7., 11.);
+            assert_eq!(TKY2JGD.bilinear(p), unindexed.bilinear(p));
+            assert_eq!(TKY2JGD.bilinear_partial(p), unindexed.bilinear_partial(p));
+        }
+        for p in [LatLon::new(20., 120.), LatLon::new(36.31, 143.)] {
+            assert_eq!(TKY2JGD.bilinear(p), None);
+        }
+    }
 }
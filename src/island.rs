@@ -0,0 +1,148 @@
+use crate::LatLon;
+
+/// 離島位置の補正量が大きい島の一覧。
+///
+/// 飛田ほか [(2003)](crate#references) が公表した表ではなく、その近似値。
+/// 補正量は、[`TKY2JGD`](crate::TKY2JGD) の島内の格子点のパラメータから、[`Tokyo97`](crate::Tokyo97) の3パラメータによる変換量を差し引いて平均したもの。
+/// 1秒 (約30m) 未満の島は含まれておらず、範囲も島を囲む矩形で近似している。
+/// 論文の値とは一致しない。
+const ISLANDS: &[Island] = &[
+    Island::new("与那国島", (24.40, 122.90), (24.50, 123.10), (4.788, 7.084)),
+    Island::new("石垣島", (24.25, 124.06), (24.65, 124.40), (4.714, 7.267)),
+    Island::new(
+        "多良間島",
+        (24.62, 124.64),
+        (24.79, 124.76),
+        (-9.151, 17.204),
+    ),
+    Island::new("宮古島", (24.68, 125.10), (24.97, 125.51), (-1.838, 2.410)),
+    Island::new(
+        "大東諸島",
+        (25.79, 131.18),
+        (25.99, 131.37),
+        (-12.075, 18.829),
+    ),
+    Island::new("硫黄島", (24.72, 141.25), (24.85, 141.42), (-25.263, 5.663)),
+    Island::new("青ヶ島", (32.42, 139.74), (32.50, 139.81), (2.439, 0.140)),
+];
+
+/// [`Tokyo`](crate::Tokyo) から [`Tokyo97`](crate::Tokyo97) への補正量。
+///
+/// いずれの島にも属さない座標では0となる。
+pub(crate) fn shift(tokyo: LatLon) -> LatLon {
+    ISLANDS
+        .iter()
+        .find(|island| island.contains(tokyo))
+        .map(Island::shift)
+        .unwrap_or_default()
+}
+
+/// [`Tokyo97`](crate::Tokyo97) から [`Tokyo`](crate::Tokyo) への補正量。
+/// Inverse of [`shift`].
+pub(crate) fn shift_inverse(tokyo97: LatLon) -> LatLon {
+    ISLANDS
+        .iter()
+        .find(|island| island.contains(tokyo97 - island.shift()))
+        .map(|island| island.shift() * -1.)
+        .unwrap_or_default()
+}
+
+/// 離島。
+/// Remote island.
+struct Island {
+    /// 島名
+    _name: &'static str,
+
+    /// 南西端の緯度経度 (度)
+    min: (f64, f64),
+
+    /// 北東端の緯度経度 (度)
+    max: (f64, f64),
+
+    /// 緯度経度の補正量 (秒)
+    shift: (f64, f64),
+}
+impl Island {
+    const fn new(name: &'static str, min: (f64, f64), max: (f64, f64), shift: (f64, f64)) -> Self {
+        Self {
+            _name: name,
+            min,
+            max,
+            shift,
+        }
+    }
+
+    fn contains(&self, p: LatLon) -> bool {
+        (self.min.0..self.max.0).contains(&p.lat()) && (self.min.1..self.max.1).contains(&p.lon())
+    }
+
+    fn shift(&self) -> LatLon {
+        LatLon::from_secs(self.shift.0, self.shift.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    #[test]
+    fn minami_daito() {
+        let ret = shift(LatLon::new(25.84, 131.24));
+        assert_abs_diff_eq!(ret.lat(), -12.075 / 3_600.);
+        assert_abs_diff_eq!(ret.lon(), 18.829 / 3_600.);
+    }
+
+    #[test]
+    fn iwo() {
+        let ret = shift(LatLon::new(24.78, 141.32));
+        assert_abs_diff_eq!(ret.lat(), -25.263 / 3_600.);
+        assert_abs_diff_eq!(ret.lon(), 5.663 / 3_600.);
+    }
+
+    #[test]
+    fn mainland() {
+        let ret = shift(LatLon::new(35.0, 135.0));
+        assert_eq!(ret, LatLon::default());
+    }
+
+    #[test]
+    fn inverse() {
+        for p in [
+            LatLon::new(24.45, 123.0),
+            LatLon::new(24.34, 124.16),
+            LatLon::new(24.66, 124.70),
+            LatLon::new(24.80, 125.30),
+            LatLon::new(32.46, 139.76),
+        ] {
+            let tokyo97 = p + shift(p);
+            assert_ne!(p, tokyo97);
+            let ret = tokyo97 + shift_inverse(tokyo97);
+            assert_abs_diff_eq!(ret.lat(), p.lat(), epsilon = 1e-12);
+            assert_abs_diff_eq!(ret.lon(), p.lon(), epsilon = 1e-12);
+        }
+    }
+
+    /// パラメータグリッドと3パラメータ＋補正量による変換が、島内でおおむね一致する。
+    #[cfg(feature = "tky2jgd")]
+    #[test]
+    fn consistent_with_tky2jgd() {
+        // 5m
+        const EPSILON: f64 = 0.00005;
+        for p in [
+            LatLon::new(24.454167, 123.006250),
+            LatLon::new(24.454167, 124.231250),
+            LatLon::new(24.679167, 124.693750),
+            LatLon::new(24.829167, 125.306250),
+            LatLon::new(25.870833, 131.256250),
+            LatLon::new(24.787500, 141.343750),
+            LatLon::new(32.462500, 139.781250),
+        ] {
+            let grid = p + crate::TKY2JGD.bilinear(p).unwrap();
+            let (lat, lon) = crate::Tokyo97::new(p + shift(p)).to_jgd2000().into();
+            assert_abs_diff_eq!(grid.lat(), lat, epsilon = EPSILON);
+            assert_abs_diff_eq!(grid.lon(), lon, epsilon = EPSILON);
+        }
+    }
+}
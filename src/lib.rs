@@ -11,6 +11,7 @@
 //! 旧日本測地系 [`Tokyo`] の北緯35度・東経135度を、世界測地系 [`Jgd2011`] に変換する。
 //!
 //! ```
+//! #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
 //! let (lat, lon) = jgd::from_tokyo(35.0, 135.0)
 //!     .to_jgd2000()
 //!     .to_jgd2011()
@@ -19,19 +20,36 @@
 //!
 //! <br>
 //!
+//! 世界測地系 [`Jgd2011`] の北緯35度・東経139度を、平面直角座標系の第IX系に投影する。
+//!
+//! ```
+//! use jgd::Zone;
+//!
+//! let (x, y) = jgd::from_jgd2011(35.0, 139.0)
+//!     .to_plane_rectangular(Zone::IX)
+//!     .into();
+//! ```
+//!
+//! <br>
+//!
 //! [`geo`](https://docs.rs/geo/latest/geo/index.html#types) の形状を測地系変換する。
 //!
 //! ```
 //! use geo::{Coord, LineString, MapCoords};
 //!
+//! # #[cfg(all(feature = "tky2jgd", feature = "patchjgd"))]
+//! # {
 //! let tokyo_datum = LineString::from(vec![(135.0, 35.0), (135.1, 35.1)]);
 //! let jgd2011 = tokyo_datum.map_coords(|Coord { x, y }| {
 //!     // 順序に注意: lat, lon <=> y, x
 //!     let (y, x) = jgd::from_tokyo(y, x).to_jgd2000().to_jgd2011().into();
 //!     Coord { x, y }
 //! });
+//! # }
 //! ```
 //!
+//! `geo-types` フィーチャーを有効にすると、`GeoTransform` で緯度経度の順序を気にせずに変換できる。
+//!
 //! # Limitations
 //!
 //! 国内の陸地を対象としている。海上や国外の座標には適さない。
@@ -39,8 +57,16 @@
 //! 一般に、測地系変換によって、ある測地系で測量・作成された座標を、あたかも別の測地系かのように模擬できる。
 //! 異なる測地系で整備された座標同士のズレを低減できても、ズレが消滅することはない。
 //! 変換メソッド毎に精度や制約が異なり、詳細はそれぞれのドキュメントに記載されている。
+//! `try_` で始まる変換メソッドは、不正な座標やパラメータグリッドの範囲外の座標を、フォールバックせずに [`Error`] とする。
+//!
+//! 緯度経度で表される地理座標のほか、[`PlaneRectangular`] (平面直角座標系), [`Utm`] および [`WebMercator`] へ投影できる。
+//! 緯度経度を含む地域メッシュは [`Mesh`] で求められる。
+//! 実行時に測地系を選ぶ場合は、[`transform`] で [`Datum`] の間を、[`Crs`] でEPSGコードの間を変換できる。
+//! [`Crs`] は WKT や PROJJSON から識別し、`.prj` ファイルの WKT を出力できる。
+//! CSV の座標の列は [`CsvTransform`] で変換できる。
 //!
-//! 緯度経度で表される地理座標のみ変換可能。平面直角座標系(XY)は未対応。
+//! 楕円体高と標高の変換には、国土地理院のジオイド・モデルを [`Geoid`] で読み込む必要がある。
+//! 同様に、セミ・ダイナミック補正には、観測した年度のパラメータを [`SemiDyna`] で読み込む必要がある。
 //!
 //! # Compatibility
 //!
@@ -55,25 +81,79 @@
 //! - `tky2jgd` - [TKY2JGD] を使用する。デフォルトで有効。
 //! - `patchjgd` - [TOUHOKUTAIHEIYOUOKI2011] を使用する。デフォルトで有効。
 //!
+//! - `geo-types` - [`geo_types`](https://docs.rs/geo-types) の形状を変換する `GeoTransform` を使用する。
+//! - `rayon` - [`tokyo_to_jgd2000`] などの一括変換を並列に実行する `par_` で始まる関数を使用する。
+//!
+//! 組み込まれていないパラメータグリッドは、[`GridBuf`] で実行時に読み込める。
+//!
 //! # References
 //!
 //! - 飛田幹男 [最近の測地座標系と座標変換についての考察](https://www.jstage.jst.go.jp/article/sokuchi1954/43/4/43_4_231/_pdf) (測地学会誌 43巻 4号 (1997) pp231-235)
 //! - 飛田幹男 [世界測地系移行のための座標変換ソフトウェア "TKY2JGD"](https://www.gsi.go.jp/common/000063173.pdf) (国土地理院時報 97集 (2001) pp31-51)
 //! - 飛田幹男ほか [日本測地系における離島位置の補正量](https://www.jstage.jst.go.jp/article/sokuchi1954/49/3/49_3_181/_pdf) (測地学会誌 49巻 3号 (2003) pp181-192)
+//! - 河瀬和重 Gauss-Krüger投影における経緯度座標及び平面直角座標相互間の座標換算についてのより簡明な計算方法 (国土地理院時報 121集 (2011) pp109-124)
 //! - 飛田幹男 [地震時地殻変動に伴う座標値の変化を補正するソフトウェア "PatchJGD"](https://www.jstage.jst.go.jp/article/sokuchi/55/4/55_4_355/_pdf/-char/ja) (測地学会誌 55巻 4号 (2009) pp355-367)
 
+mod batch;
 mod coord;
 mod crs;
+mod csv;
+mod date;
+mod datum;
 mod earth;
+mod epsg;
+mod error;
+mod geoid;
+#[cfg(feature = "geo-types")]
+mod geometry;
 mod grid;
+#[cfg(feature = "tky2jgd")]
 mod island;
-#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+mod mercator;
+mod mesh;
+mod method;
 mod par;
+mod patch;
+mod plane;
+mod projjson;
+mod semidyna;
+mod shape;
+mod tm;
+mod utm;
+mod wkt;
 
-pub use coord::LatLon;
-pub use crs::{from_jgd2000, from_tokyo, from_tokyo97, Jgd2000, Jgd2011, Tokyo, Tokyo97};
-pub use grid::Grid;
+pub use batch::Degrees;
+#[cfg(feature = "patchjgd")]
+pub use batch::{jgd2000_to_jgd2011, jgd2011_to_jgd2000};
+#[cfg(feature = "tky2jgd")]
+pub use batch::{jgd2000_to_tokyo, tokyo_to_jgd2000};
+#[cfg(all(feature = "rayon", feature = "patchjgd"))]
+pub use batch::{par_jgd2000_to_jgd2011, par_jgd2011_to_jgd2000};
+#[cfg(all(feature = "rayon", feature = "tky2jgd"))]
+pub use batch::{par_jgd2000_to_tokyo, par_tokyo_to_jgd2000};
+pub use coord::{LatLon, LatLonHeight};
+pub use crs::{
+    from_jgd2000, from_jgd2000_3d, from_jgd2011, from_jgd2011_3d, from_tokyo, from_tokyo97,
+    from_tokyo97_3d, from_tokyo_3d, Geographic, Jgd2000, Jgd2011, Tokyo, Tokyo97,
+};
+pub use csv::{Column, CsvTransform};
+pub use date::Date;
+pub use datum::{transform, transform_with_method, Datum};
+pub use epsg::{Crs, Projection};
+pub use error::Error;
+pub use geoid::Geoid;
+#[cfg(feature = "geo-types")]
+pub use geometry::GeoTransform;
 #[cfg(feature = "tky2jgd")]
 pub use grid::TKY2JGD;
 #[cfg(feature = "patchjgd")]
 pub use grid::TOUHOKUTAIHEIYOUOKI2011;
+pub use grid::{Displacement, Grid, GridBuf, IndexedGrid, MicroMeter, MicroSecond, Parameter};
+pub use mercator::{Tile, WebMercator};
+pub use mesh::{Mesh, MeshLevel};
+pub use method::{Interpolation, Method};
+pub use patch::{Patch, PATCHES};
+pub use plane::{PlaneRectangular, Zone};
+pub use semidyna::SemiDyna;
+pub use shape::{Shape, Strategy};
+pub use utm::Utm;
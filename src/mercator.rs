@@ -0,0 +1,272 @@
+use std::{
+    f64::consts::{FRAC_PI_4, PI},
+    fmt::Display,
+    marker::PhantomData,
+};
+
+use crate::{crs::Geographic, LatLon};
+
+/// Webメルカトル座標系。
+/// Web Mercator projection.
+///
+/// `T` は基準とする測地系で、[`Jgd2000`](crate::Jgd2000), [`Jgd2011`](crate::Jgd2011) のいずれか。
+/// 地理院タイルなどのXYZタイルで使われる。
+///
+/// EPSG: 3857
+///
+/// # Limitations
+///
+/// 本来はWGS84を基準とするが、世界測地系との差は地図の表示には影響しないため、区別しない。
+///
+/// # Examples
+///
+/// ```
+/// let tile = jgd::from_jgd2011(35.681236, 139.767125)
+///     .to_web_mercator()
+///     .tile(18);
+/// let url = format!("https://cyberjapandata.gsi.go.jp/xyz/std/{}.png", tile);
+/// ```
+pub struct WebMercator<T> {
+    x: f64,
+    y: f64,
+    datum: PhantomData<T>,
+}
+impl<T: Geographic> WebMercator<T> {
+    /// EPSGコード。
+    /// EPSG code.
+    pub const EPSG: u32 = 3857;
+
+    /// 球の半径 (メートル)
+    const RADIUS: f64 = 6378137.0;
+
+    /// 投影できる最大の緯度 (度)
+    const MAX_LAT: f64 = 85.0511287798066;
+
+    /// ピクセル単位のタイルの大きさ
+    const TILE_SIZE: f64 = 256.;
+
+    /// Webメルカトル座標から変換する。
+    /// Transform from a coordinate in Web Mercator.
+    ///
+    /// `x` は東向き、`y` は北向きの、経緯度原点からの距離 (メートル)。
+    pub fn new(x: f64, y: f64) -> Self {
+        Self {
+            x,
+            y,
+            datum: PhantomData,
+        }
+    }
+
+    pub(crate) fn from_degrees(degrees: LatLon) -> Self {
+        let lat = degrees
+            .lat()
+            .clamp(-Self::MAX_LAT, Self::MAX_LAT)
+            .to_radians();
+        let x = Self::RADIUS * degrees.lon().to_radians();
+        let y = Self::RADIUS * (FRAC_PI_4 + lat / 2.).tan().ln();
+        Self::new(x, y)
+    }
+
+    /// 緯度経度へ逆投影する。
+    /// Inverse projection to latitude and longitude.
+    pub fn to_geographic(&self) -> T {
+        let lat = (self.y / Self::RADIUS).sinh().atan();
+        let lon = self.x / Self::RADIUS;
+        T::new(LatLon::new(lat.to_degrees(), lon.to_degrees()))
+    }
+
+    /// 東向きのX座標 (メートル)。
+    /// Easting in meters.
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// 北向きのY座標 (メートル)。
+    /// Northing in meters.
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// 指定されたズームレベルで、この座標を含むタイル。
+    /// Tile containing this coordinate at the zoom level.
+    ///
+    /// # Panics
+    ///
+    /// ズームレベルが31以上の場合。
+    pub fn tile(&self, zoom: u8) -> Tile {
+        let (x, y) = self.world_pixel(zoom);
+        let max = Tile::count(zoom) - 1;
+        let x = ((x / Self::TILE_SIZE) as u32).min(max);
+        let y = ((y / Self::TILE_SIZE) as u32).min(max);
+        Tile::new(zoom, x, y)
+    }
+
+    /// 指定されたズームレベルで、タイルの左上を原点とした、この座標のピクセル位置。
+    /// Pixel position from the top-left corner of the tile at the zoom level.
+    ///
+    /// タイルの大きさは256ピクセル。
+    ///
+    /// # Panics
+    ///
+    /// ズームレベルが31以上の場合。
+    pub fn pixel(&self, zoom: u8) -> (f64, f64) {
+        let (x, y) = self.world_pixel(zoom);
+        let tile = self.tile(zoom);
+        (
+            x - f64::from(tile.x) * Self::TILE_SIZE,
+            y - f64::from(tile.y) * Self::TILE_SIZE,
+        )
+    }
+
+    /// 世界全体の左上を原点としたピクセル位置
+    fn world_pixel(&self, zoom: u8) -> (f64, f64) {
+        let circumference = 2. * PI * Self::RADIUS;
+        let size = f64::from(Tile::count(zoom)) * Self::TILE_SIZE;
+        let x = (self.x / circumference + 0.5) * size;
+        let y = (0.5 - self.y / circumference) * size;
+        (x.clamp(0., size), y.clamp(0., size))
+    }
+}
+impl<T> From<WebMercator<T>> for (f64, f64) {
+    /// メートル単位のXとYのペア。
+    /// Easting and northing in meters.
+    fn from(mercator: WebMercator<T>) -> Self {
+        (mercator.x, mercator.y)
+    }
+}
+
+/// XYZタイル。
+/// XYZ map tile.
+///
+/// 文字列に変換すると `z/x/y` となる。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tile {
+    /// ズームレベル。
+    /// Zoom level.
+    pub z: u8,
+
+    /// 西から数えたタイル番号。
+    /// Column from the west.
+    pub x: u32,
+
+    /// 北から数えたタイル番号。
+    /// Row from the north.
+    pub y: u32,
+}
+impl Tile {
+    /// タイル番号から変換する。
+    /// Converts from the tile numbers.
+    ///
+    /// # Panics
+    ///
+    /// ズームレベルが31以上の場合、またはタイル番号が `2^z` 以上の場合。
+    pub fn new(z: u8, x: u32, y: u32) -> Self {
+        assert!(z < 31, "zoom level must be less than 31");
+        let count = Self::count(z);
+        assert!(
+            x < count && y < count,
+            "tile number out of range: {z}/{x}/{y}"
+        );
+        Self { z, x, y }
+    }
+
+    /// タイルの南西端と北東端の緯度経度。
+    /// Latitude and longitude of the southwest and northeast corners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Tile;
+    ///
+    /// let (sw, ne) = Tile::new(18, 232847, 103226).bounds();
+    /// ```
+    pub fn bounds(&self) -> (LatLon, LatLon) {
+        let sw = Self::corner(self.z, self.x, self.y + 1);
+        let ne = Self::corner(self.z, self.x + 1, self.y);
+        (sw, ne)
+    }
+
+    /// タイルの北西端の緯度経度
+    fn corner(z: u8, x: u32, y: u32) -> LatLon {
+        let n = f64::from(Self::count(z));
+        let lat = (PI * (1. - 2. * f64::from(y) / n)).sinh().atan();
+        let lon = f64::from(x) / n * 360. - 180.;
+        LatLon::new(lat.to_degrees(), lon)
+    }
+
+    /// 東西および南北のタイル数
+    fn count(z: u8) -> u32 {
+        1 << z
+    }
+}
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}/{}", self.z, self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::Jgd2011;
+
+    /// 東京駅
+    fn tokyo_station() -> WebMercator<Jgd2011> {
+        WebMercator::from_degrees(LatLon::new(35.681236, 139.767125))
+    }
+
+    #[test]
+    fn forward() {
+        let (x, y) = tokyo_station().into();
+        assert_abs_diff_eq!(x, 15558805.184639815, epsilon = 1e-6);
+        assert_abs_diff_eq!(y, 4256848.120219802, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn inverse() {
+        let (lat, lon) = tokyo_station().to_geographic().degrees().into();
+        assert_abs_diff_eq!(lat, 35.681236, epsilon = 1e-12);
+        assert_abs_diff_eq!(lon, 139.767125, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn tile() {
+        let tile = tokyo_station().tile(18);
+        assert_eq!(tile, Tile::new(18, 232847, 103226));
+        assert_eq!(tile.to_string(), "18/232847/103226");
+
+        let (x, y) = tokyo_station().pixel(18);
+        assert_abs_diff_eq!(x, 80.50915554910898, epsilon = 1e-6);
+        assert_abs_diff_eq!(y, 138.7541762366891, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn tile_bounds() {
+        let (sw, ne) = Tile::new(18, 232847, 103226).bounds();
+        assert_abs_diff_eq!(sw.lat(), 35.68072511370389, epsilon = 1e-12);
+        assert_abs_diff_eq!(sw.lon(), 139.76669311523438, epsilon = 1e-12);
+        assert_abs_diff_eq!(ne.lat(), 35.68184060244454, epsilon = 1e-12);
+        assert_abs_diff_eq!(ne.lon(), 139.76806640625, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn whole_world() {
+        let sut = WebMercator::<Jgd2011>::from_degrees(LatLon::new(90., 180.));
+        assert_eq!(sut.tile(0), Tile::new(0, 0, 0));
+        assert_eq!(sut.tile(1), Tile::new(1, 1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "tile number out of range")]
+    fn tile_out_of_range() {
+        Tile::new(1, 2, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "zoom level")]
+    fn tile_zoom_too_large() {
+        WebMercator::<Jgd2011>::from_degrees(LatLon::new(35., 135.)).tile(31);
+    }
+}
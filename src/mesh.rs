@@ -0,0 +1,483 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Error, LatLon};
+
+/// 地域メッシュの区分。
+/// Level of Japanese regional mesh (JIS X 0410).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MeshLevel {
+    /// 1次メッシュ (緯度40分, 経度1度, 約80km)。4桁。
+    /// 1st mesh, about 80 km.
+    First,
+
+    /// 2次メッシュ (緯度5分, 経度7分30秒, 約10km)。6桁。
+    /// 2nd mesh, about 10 km.
+    Second,
+
+    /// 3次メッシュ (基準地域メッシュ, 緯度30秒, 経度45秒, 約1km)。8桁。
+    /// 3rd mesh, about 1 km.
+    Third,
+
+    /// 2分の1地域メッシュ (約500m)。9桁。
+    /// Half mesh, about 500 m.
+    Half,
+
+    /// 4分の1地域メッシュ (約250m)。10桁。
+    /// Quarter mesh, about 250 m.
+    Quarter,
+
+    /// 8分の1地域メッシュ (約125m)。11桁。
+    /// Eighth mesh, about 125 m.
+    Eighth,
+
+    /// 100mメッシュ (3次メッシュを縦横10等分)。10桁。
+    /// 100 m mesh, the 3rd mesh divided into 10 by 10.
+    Hundred,
+}
+impl MeshLevel {
+    /// 1次メッシュを縦横に分割した数
+    fn divisions(self) -> u32 {
+        match self {
+            Self::First => 1,
+            Self::Second => 8,
+            Self::Third => 80,
+            Self::Half => 160,
+            Self::Quarter => 320,
+            Self::Eighth => 640,
+            Self::Hundred => 800,
+        }
+    }
+
+    /// メッシュコードの桁数
+    fn digits(self) -> u32 {
+        match self {
+            Self::First => 4,
+            Self::Second => 6,
+            Self::Third => 8,
+            Self::Half => 9,
+            Self::Quarter | Self::Hundred => 10,
+            Self::Eighth => 11,
+        }
+    }
+
+    /// 3次メッシュを2分割した回数
+    fn halvings(self) -> u32 {
+        match self {
+            Self::Half => 1,
+            Self::Quarter => 2,
+            Self::Eighth => 3,
+            _ => 0,
+        }
+    }
+
+    /// 桁数から推定する。10桁は4分の1地域メッシュとみなす。
+    fn from_digits(digits: usize) -> Option<Self> {
+        let level = match digits {
+            4 => Self::First,
+            6 => Self::Second,
+            8 => Self::Third,
+            9 => Self::Half,
+            10 => Self::Quarter,
+            11 => Self::Eighth,
+            _ => return None,
+        };
+        Some(level)
+    }
+}
+
+/// 地域メッシュ。
+/// Japanese regional mesh (JIS X 0410).
+///
+/// 緯度0度・東経100度を原点として、緯度40分・経度1度の1次メッシュを順に分割したもの。
+/// 緯度経度は測地系を区別しない。
+///
+/// 文字列に変換すると、区切りのないメッシュコードとなる。
+///
+/// # Examples
+///
+/// ```
+/// use jgd::{Mesh, MeshLevel};
+///
+/// let p = jgd::from_jgd2011(35.681236, 139.767125).degrees();
+/// let mesh = Mesh::from_lat_lon(p, MeshLevel::Third).unwrap();
+/// assert_eq!(mesh.to_string(), "53394611");
+///
+/// let mesh: Mesh = "53394611".parse().unwrap();
+/// let (sw, ne) = mesh.bounds();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mesh {
+    level: MeshLevel,
+
+    /// 緯度0度から数えた通し番号
+    lat: u32,
+
+    /// 東経100度から数えた通し番号
+    lon: u32,
+}
+impl Mesh {
+    /// 1次メッシュの数
+    const FIRST: u32 = 100;
+
+    /// 1次メッシュの緯度方向の大きさ (度)
+    const FIRST_LAT: f64 = 2. / 3.;
+
+    /// 西端の経度 (度)
+    const MIN_LON: f64 = 100.;
+
+    /// 緯度経度を含むメッシュ。
+    /// Mesh containing the latitude and longitude.
+    ///
+    /// 緯度0度以上66度40分未満、東経100度以上200度未満でなければ `None` を返す。
+    pub fn from_lat_lon(p: LatLon, level: MeshLevel) -> Option<Self> {
+        let n = f64::from(level.divisions());
+        let lat = (p.lat() * 1.5 * n).floor();
+        let lon = ((p.lon() - Self::MIN_LON) * n).floor();
+        let max = f64::from(Self::FIRST * level.divisions());
+        if !(0. <= lat && lat < max && 0. <= lon && lon < max) {
+            return None;
+        }
+        Some(Self {
+            level,
+            lat: lat as u32,
+            lon: lon as u32,
+        })
+    }
+
+    /// 整数のメッシュコードから変換する。
+    /// Converts from a mesh code in integer.
+    ///
+    /// 4分の1地域メッシュと100mメッシュはいずれも10桁のため、区分を指定する。
+    ///
+    /// # Errors
+    ///
+    /// 区分に対してコードが不正な場合は [`Error::InvalidMeshCode`] を返す。
+    pub fn from_code(code: u64, level: MeshLevel) -> Result<Self, Error> {
+        Self::decode(code, level).ok_or(Error::InvalidMeshCode)
+    }
+
+    /// 文字列のメッシュコードを、区分を指定して変換する。
+    /// Parses a mesh code of the level.
+    ///
+    /// 区分を指定しない場合は [`str::parse`] を用いる。
+    ///
+    /// # Errors
+    ///
+    /// 桁数が区分と一致しないか、コードが不正な場合は [`Error::InvalidMeshCode`] を返す。
+    pub fn parse(code: &str, level: MeshLevel) -> Result<Self, Error> {
+        let valid =
+            code.len() == level.digits() as usize && code.bytes().all(|b| b.is_ascii_digit());
+        match valid {
+            true => Self::from_code(code.parse().map_err(|_| Error::InvalidMeshCode)?, level),
+            false => Err(Error::InvalidMeshCode),
+        }
+    }
+
+    /// 区分。
+    /// Level of the mesh.
+    pub fn level(&self) -> MeshLevel {
+        self.level
+    }
+
+    /// 整数のメッシュコード。
+    /// Mesh code in integer.
+    pub fn code(&self) -> u64 {
+        let (lat, lon) = (u64::from(self.lat), u64::from(self.lon));
+        match self.level {
+            MeshLevel::First | MeshLevel::Second | MeshLevel::Third => {
+                encode(lat, lon, self.level.divisions())
+            }
+            MeshLevel::Hundred => encode(lat / 10, lon / 10, 80) * 100 + lat % 10 * 10 + lon % 10,
+            _ => {
+                let k = self.level.halvings();
+                (0..k)
+                    .rev()
+                    .fold(encode(lat >> k, lon >> k, 80), |code, i| {
+                        code * 10 + 1 + 2 * (lat >> i & 1) + (lon >> i & 1)
+                    })
+            }
+        }
+    }
+
+    /// メッシュの南西端と北東端の緯度経度。
+    /// Latitude and longitude of the southwest and northeast corners.
+    pub fn bounds(&self) -> (LatLon, LatLon) {
+        let sw = self.corner(self.lat, self.lon);
+        let ne = self.corner(self.lat + 1, self.lon + 1);
+        (sw, ne)
+    }
+
+    /// メッシュの中心の緯度経度。
+    /// Latitude and longitude of the center.
+    pub fn center(&self) -> LatLon {
+        let (sw, ne) = self.bounds();
+        (sw + ne) / 2.
+    }
+
+    /// 北へ `north` 個、東へ `east` 個ずれた、同じ区分のメッシュ。
+    /// Mesh of the same level offset by `north` rows and `east` columns.
+    ///
+    /// 負の値は南または西を表す。範囲外になる場合は `None` を返す。
+    pub fn offset(&self, north: i32, east: i32) -> Option<Self> {
+        let max = Self::FIRST * self.level.divisions();
+        let shift = |i: u32, d: i32| i.checked_add_signed(d).filter(|&i| i < max);
+        Some(Self {
+            level: self.level,
+            lat: shift(self.lat, north)?,
+            lon: shift(self.lon, east)?,
+        })
+    }
+
+    /// 隣接する最大8つのメッシュ。
+    /// Up to 8 adjacent meshes.
+    ///
+    /// 北から時計回りに、北, 北東, 東, 南東, 南, 南西, 西, 北西の順。
+    /// 範囲外のメッシュは含まない。
+    pub fn neighbors(&self) -> Vec<Self> {
+        [
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+        ]
+        .into_iter()
+        .filter_map(|(north, east)| self.offset(north, east))
+        .collect()
+    }
+
+    /// 緯度0度・経度0度から数えた通し番号
+    pub(crate) fn serial(&self) -> (u32, u32) {
+        let n = Self::MIN_LON as u32 * self.level.divisions();
+        (self.lat, self.lon + n)
+    }
+
+    /// 1段階大きい区分の、このメッシュを含むメッシュ。
+    /// Mesh of the next larger level containing this mesh.
+    ///
+    /// 100mメッシュは3次メッシュに含まれる。1次メッシュは `None` を返す。
+    pub fn parent(&self) -> Option<Self> {
+        let level = match self.level {
+            MeshLevel::First => return None,
+            MeshLevel::Second => MeshLevel::First,
+            MeshLevel::Third => MeshLevel::Second,
+            MeshLevel::Half | MeshLevel::Hundred => MeshLevel::Third,
+            MeshLevel::Quarter => MeshLevel::Half,
+            MeshLevel::Eighth => MeshLevel::Quarter,
+        };
+        let ratio = self.level.divisions() / level.divisions();
+        Some(Self {
+            level,
+            lat: self.lat / ratio,
+            lon: self.lon / ratio,
+        })
+    }
+
+    /// 通し番号の南西端の緯度経度
+    fn corner(&self, lat: u32, lon: u32) -> LatLon {
+        let n = f64::from(self.level.divisions());
+        LatLon::new(
+            f64::from(lat) * Self::FIRST_LAT / n,
+            Self::MIN_LON + f64::from(lon) / n,
+        )
+    }
+
+    fn decode(code: u64, level: MeshLevel) -> Option<Self> {
+        if 10_u64.pow(level.digits()) <= code {
+            return None;
+        }
+        let (lat, lon) = match level {
+            MeshLevel::First | MeshLevel::Second | MeshLevel::Third => {
+                decode(code, level.divisions())?
+            }
+            MeshLevel::Hundred => {
+                let (lat, lon) = decode(code / 100, 80)?;
+                (lat * 10 + code / 10 % 10, lon * 10 + code % 10)
+            }
+            _ => {
+                let k = level.halvings();
+                let (lat, lon) = decode(code / 10_u64.pow(k), 80)?;
+                (0..k).rev().try_fold((lat, lon), |(lat, lon), i| {
+                    let digit = code / 10_u64.pow(i) % 10;
+                    let quadrant = digit.checked_sub(1).filter(|&q| q < 4)?;
+                    Some((lat << 1 | quadrant >> 1, lon << 1 | quadrant & 1))
+                })?
+            }
+        };
+        Some(Self {
+            level,
+            lat: lat.try_into().ok()?,
+            lon: lon.try_into().ok()?,
+        })
+    }
+}
+impl Display for Mesh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self.level.digits() as usize;
+        write!(f, "{:0width$}", self.code())
+    }
+}
+impl FromStr for Mesh {
+    type Err = Error;
+
+    /// 桁数から区分を推定する。
+    ///
+    /// 10桁は4分の1地域メッシュとみなす。100mメッシュは [`Mesh::parse`] で区分を指定する。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let level = MeshLevel::from_digits(s.len()).ok_or(Error::InvalidMeshCode)?;
+        Self::parse(s, level)
+    }
+}
+
+/// 1次から3次メッシュまでの通し番号をコードにする。`n` は1次メッシュの分割数
+fn encode(lat: u64, lon: u64, n: u32) -> u64 {
+    let first = lat / u64::from(n) * 100 + lon / u64::from(n);
+    match n {
+        1 => first,
+        8 => first * 100 + lat % 8 * 10 + lon % 8,
+        _ => (first * 100 + lat / 10 % 8 * 10 + lon / 10 % 8) * 100 + lat % 10 * 10 + lon % 10,
+    }
+}
+
+/// [`encode`] の逆。2次メッシュの各桁は8未満でなければならない
+fn decode(code: u64, n: u32) -> Option<(u64, u64)> {
+    let (first, second, third) = match n {
+        1 => (code, None, None),
+        8 => (code / 100, Some(code % 100), None),
+        _ => (code / 10_000, Some(code / 100 % 100), Some(code % 100)),
+    };
+    let (mut lat, mut lon) = (first / 100, first % 100);
+    if let Some(second) = second {
+        let (q, v) = (second / 10, second % 10);
+        if 8 <= q || 8 <= v {
+            return None;
+        }
+        (lat, lon) = (lat * 8 + q, lon * 8 + v);
+    }
+    if let Some(third) = third {
+        (lat, lon) = (lat * 10 + third / 10, lon * 10 + third % 10);
+    }
+    Some((lat, lon))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+
+    /// 東京駅
+    const TOKYO_STATION: (f64, f64) = (35.681236, 139.767125);
+
+    fn mesh(level: MeshLevel) -> Mesh {
+        let (lat, lon) = TOKYO_STATION;
+        Mesh::from_lat_lon(LatLon::new(lat, lon), level).unwrap()
+    }
+
+    #[test]
+    fn code() {
+        for (level, exp) in [
+            (MeshLevel::First, "5339"),
+            (MeshLevel::Second, "533946"),
+            (MeshLevel::Third, "53394611"),
+            (MeshLevel::Half, "533946113"),
+            (MeshLevel::Quarter, "5339461132"),
+            (MeshLevel::Eighth, "53394611323"),
+            (MeshLevel::Hundred, "5339461173"),
+        ] {
+            let sut = mesh(level);
+            assert_eq!(sut.to_string(), exp);
+            assert_eq!(sut.code(), exp.parse::<u64>().unwrap());
+            assert_eq!(Mesh::parse(exp, level), Ok(sut));
+            assert_eq!(Mesh::from_code(sut.code(), level), Ok(sut));
+        }
+    }
+
+    #[test]
+    fn parse_infers_level() {
+        assert_eq!("53394611".parse(), Ok(mesh(MeshLevel::Third)));
+        assert_eq!("5339461132".parse(), Ok(mesh(MeshLevel::Quarter)));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for code in [
+            "",
+            "533",
+            "53394811",
+            "5339461105",
+            "533946113x",
+            "+5339461",
+        ] {
+            assert_eq!(code.parse::<Mesh>(), Err(Error::InvalidMeshCode), "{code}");
+        }
+        assert!(Mesh::parse("53394611", MeshLevel::Second).is_err());
+        assert!(Mesh::from_code(100_000_000, MeshLevel::Third).is_err());
+    }
+
+    #[test]
+    fn bounds() {
+        let (sw, ne) = mesh(MeshLevel::Third).bounds();
+        assert_abs_diff_eq!(sw.lat(), 35. + 40. / 60. + 30. / 3600., epsilon = 1e-12);
+        assert_abs_diff_eq!(sw.lon(), 139. + 45. / 60. + 45. / 3600., epsilon = 1e-12);
+        assert_abs_diff_eq!(ne.lat(), 35. + 41. / 60., epsilon = 1e-12);
+        assert_abs_diff_eq!(ne.lon(), 139. + 45. / 60. + 90. / 3600., epsilon = 1e-12);
+
+        let center = mesh(MeshLevel::Eighth).center();
+        assert_eq!(
+            Mesh::from_lat_lon(center, MeshLevel::Eighth),
+            Some(mesh(MeshLevel::Eighth))
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let sut = mesh(MeshLevel::Third);
+        let ret = sut.neighbors();
+        assert_eq!(ret.len(), 8);
+        assert_eq!(ret[0].to_string(), "53394621");
+        assert_eq!(ret[2].to_string(), "53394612");
+        // 2次メッシュの境界をまたぐ
+        assert_eq!(sut.offset(0, -2).unwrap().to_string(), "53394519");
+
+        let corner = Mesh::from_lat_lon(LatLon::new(0., 100.), MeshLevel::First).unwrap();
+        assert_eq!(corner.to_string(), "0000");
+        assert_eq!(corner.neighbors().len(), 3);
+    }
+
+    #[test]
+    fn parent() {
+        assert_eq!(
+            mesh(MeshLevel::Eighth).parent(),
+            Some(mesh(MeshLevel::Quarter))
+        );
+        assert_eq!(
+            mesh(MeshLevel::Hundred).parent(),
+            Some(mesh(MeshLevel::Third))
+        );
+        assert_eq!(
+            mesh(MeshLevel::Second).parent(),
+            Some(mesh(MeshLevel::First))
+        );
+        assert_eq!(mesh(MeshLevel::First).parent(), None);
+    }
+
+    #[test]
+    fn outside() {
+        for (lat, lon) in [
+            (-0.1, 135.),
+            (66.7, 135.),
+            (35., 99.9),
+            (35., 200.),
+            (f64::NAN, 135.),
+        ] {
+            assert_eq!(
+                Mesh::from_lat_lon(LatLon::new(lat, lon), MeshLevel::Third),
+                None
+            );
+        }
+    }
+}
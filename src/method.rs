@@ -0,0 +1,98 @@
+/// パラメータグリッドの補間方法。
+/// Interpolation of parameters grid.
+///
+/// 指定された座標が属する3次メッシュの四隅すべてにパラメータが存在すれば、いずれもバイリニア補間となる。
+/// 一部が欠けている場合の扱いが異なる。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Interpolation {
+    /// 四隅すべてが存在する場合のみ補間する。
+    /// Bilinear interpolation only. See [`Grid::bilinear`](crate::Grid::bilinear).
+    #[default]
+    Bilinear,
+
+    /// 存在する隅のみで補間する。
+    /// Interpolation from the existing corners. See [`Grid::bilinear_partial`](crate::Grid::bilinear_partial).
+    Partial,
+
+    /// `limit` メートル以内で最も近い格子点のパラメータを用いる。
+    /// Nearest grid point within `limit` meters. See [`Grid::nearest`](crate::Grid::nearest).
+    Nearest {
+        /// 格子点までの距離の上限 (メートル)
+        limit: f64,
+    },
+}
+
+/// 座標変換に実際に用いられた方法。
+/// Method actually applied to a transformation.
+///
+/// [`Tokyo::to_jgd2000_with_method`](crate::Tokyo::to_jgd2000_with_method) などで、変換後の座標とともに返される。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// 四隅すべてのパラメータによるバイリニア補間。
+    /// Bilinear interpolation of the parameters grid.
+    Bilinear,
+
+    /// 四隅の一部のパラメータによる補間。
+    /// Interpolation from a part of the corners.
+    Partial,
+
+    /// 最近傍の格子点のパラメータ。
+    /// Parameters of the nearest grid point.
+    Nearest,
+
+    /// パラメータグリッドの範囲外で、3パラメータ [(飛田, 2001)](crate#references) による変換にフォールバックした。
+    /// Fallen back to the 3-parameter transformation.
+    ThreeParameter,
+
+    /// パラメータグリッドの範囲外で、何も行われなかった。
+    /// Nothing has been done.
+    Identity,
+}
+impl Method {
+    /// 精度の目安 (メートル)。
+    /// Indicative accuracy in meters.
+    ///
+    /// 方法ごとの大まかな値であり、個々の座標の誤差を保証するものではない。
+    ///
+    /// | 方法 | 目安 | 根拠 |
+    /// |---|---|---|
+    /// | [`Bilinear`](Method::Bilinear) | 0.1 | TKY2JGD の「緯度, 経度の標準偏差はそれぞれ9cm, 8cm」[(飛田, 2001)](crate#references) |
+    /// | [`Partial`](Method::Partial) | 1 | 欠けた隅のパラメータとの差は、一般に隣接する格子点間の差と同程度 |
+    /// | [`Nearest`](Method::Nearest) | 1 | 同上。ただし格子点から離れるほど大きくなる |
+    /// | [`ThreeParameter`](Method::ThreeParameter) | 5 | 地域ごとのひずみを補正しないため、数メートルずれる |
+    /// | [`Identity`](Method::Identity) | 不明 | `None` を返す |
+    pub fn accuracy(&self) -> Option<f64> {
+        match self {
+            Self::Bilinear => Some(0.1),
+            Self::Partial | Self::Nearest => Some(1.),
+            Self::ThreeParameter => Some(5.),
+            Self::Identity => None,
+        }
+    }
+
+    /// パラメータグリッドを用いた変換かどうか。
+    /// Whether the parameters grid is used.
+    pub fn is_grid(&self) -> bool {
+        matches!(self, Self::Bilinear | Self::Partial | Self::Nearest)
+    }
+
+    /// 複数の変換を経由した場合に、全体を代表する方法。
+    ///
+    /// 何も行われなかった [`Identity`](Method::Identity) を除き、精度の低い方を返す。
+    pub(crate) fn coarser(self, other: Self) -> Self {
+        fn rank(method: Method) -> u8 {
+            match method {
+                Method::Identity => 0,
+                Method::Bilinear => 1,
+                Method::Partial => 2,
+                Method::Nearest => 3,
+                Method::ThreeParameter => 4,
+            }
+        }
+        if rank(other) > rank(self) {
+            other
+        } else {
+            self
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
 use std::{mem, slice};
 
-use crate::{grid::Dot, Grid};
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+use crate::Grid;
+use crate::{grid::Dot, Mesh, MeshLevel, Parameter};
 
 #[cfg(feature = "tky2jgd")]
 pub const TKY2JGD: Bin<4707876> = Bin(*include_bytes!("../par/TKY2JGD.in"));
@@ -10,13 +14,15 @@ pub const TOUHOKUTAIHEIYOUOKI2011: Bin<1932636> =
     Bin(*include_bytes!("../par/touhokutaiheiyouoki2011.in"));
 
 // wrap bytes to align
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
 #[repr(align(4))]
 pub struct Bin<const N: usize>([u8; N]);
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
 impl<const N: usize> Bin<N> {
-    pub const fn to_grid(&self) -> Grid {
+    pub const fn to_grid<S>(&self) -> Grid<'_, S> {
         assert!(isize::MAX as usize > N);
-        let data = self.0.as_ptr() as *const Dot;
-        let len = self.0.len() / mem::size_of::<Dot>();
+        let data = self.0.as_ptr() as *const Dot<S>;
+        let len = self.0.len() / mem::size_of::<Dot<S>>();
 
         #[cfg(not(target_endian = "little"))]
         compile_error!("compile target must be little endian");
@@ -29,3 +35,117 @@ impl<const N: usize> Bin<N> {
         Grid::new(dots)
     }
 }
+
+/// 国土地理院の `.par` 形式を読み込む。
+///
+/// ヘッダー行 (例: `MeshCode   dB(sec)   dL(sec)`) より前の行は読み飛ばす。
+/// 各行は8桁の3次メッシュコードと、10文字幅の右詰めのパラメータからなる。
+/// 幅が揃っていない行は、空白で区切られたパラメータとして読み込む。
+pub(crate) fn parse<S: Parameter>(reader: impl Read) -> io::Result<Vec<Dot<S>>> {
+    let mut lines = BufReader::new(reader).lines();
+    let header = loop {
+        let line = lines
+            .next()
+            .ok_or_else(|| invalid("missing MeshCode header"))??;
+        if line.starts_with("MeshCode") {
+            break line;
+        }
+    };
+    let columns = header.split_whitespace().skip(1).collect::<Vec<_>>();
+    if columns != S::COLUMNS {
+        return Err(invalid(format!("unexpected columns: {}", header)));
+    }
+
+    let mut dots = Vec::new();
+    for line in lines {
+        let line = line?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        dots.push(parse_line(line).ok_or_else(|| invalid(format!("invalid line: {}", line)))?);
+    }
+    Ok(dots)
+}
+
+fn parse_line<S: Parameter>(line: &str) -> Option<Dot<S>> {
+    let mesh = Mesh::parse(line.get(..8)?, MeshLevel::Third).ok()?;
+
+    // Serial number of 3rd mesh grids starting from 0 degree
+    let (lat, lon) = mesh.serial();
+    let (lat, lon) = (i16::try_from(lat).ok()?, i16::try_from(lon).ok()?);
+
+    let values = parse_fixed_width(&line[8..], S::COLUMNS.len())
+        .or_else(|| parse_whitespace_separated(&line[8..], S::COLUMNS.len()))?;
+
+    Some(Dot::new(lat, lon, S::from_par(&values)?))
+}
+
+/// パラメータの列の幅
+const COLUMN_WIDTH: usize = 10;
+
+/// 10文字幅の列。`-123.45678-123.45678` のように、値の間に空白がない場合も読み込める
+fn parse_fixed_width(s: &str, columns: usize) -> Option<Vec<f64>> {
+    if s.len() != COLUMN_WIDTH * columns || !s.is_ascii() {
+        return None;
+    }
+    s.as_bytes()
+        .chunks_exact(COLUMN_WIDTH)
+        .map(|column| std::str::from_utf8(column).ok()?.trim().parse().ok())
+        .collect()
+}
+
+fn parse_whitespace_separated(s: &str, columns: usize) -> Option<Vec<f64>> {
+    let values = s
+        .split_whitespace()
+        .map(|value| value.parse().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    (values.len() == columns).then_some(values)
+}
+
+/// `.in` 形式を読み込む。
+///
+/// 各格子点は、リトルエンディアンの3次メッシュの通し番号 (`i16` 緯度, 経度) と、パラメータ (`i32` マイクロ単位) からなる。
+pub(crate) fn read<S: Parameter>(mut reader: impl Read) -> io::Result<Vec<Dot<S>>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let size = 4 + 4 * S::COLUMNS.len();
+    if bytes.len() % size != 0 {
+        return Err(invalid("truncated record"));
+    }
+    let dots = bytes
+        .chunks_exact(size)
+        .map(|record| {
+            let lat = i16::from_le_bytes([record[0], record[1]]);
+            let lon = i16::from_le_bytes([record[2], record[3]]);
+            let micros = record[4..]
+                .chunks_exact(4)
+                .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect::<Vec<_>>();
+            Dot::new(lat, lon, S::from_micros(&micros))
+        })
+        .collect::<Vec<_>>();
+
+    if !dots.windows(2).all(|w| w[0].mesh() < w[1].mesh()) {
+        return Err(invalid("records must be sorted"));
+    }
+    Ok(dots)
+}
+
+/// `.in` 形式で書き出す。
+pub(crate) fn write<S: Parameter>(dots: &[Dot<S>], mut writer: impl Write) -> io::Result<()> {
+    for dot in dots {
+        let (lat, lon) = dot.mesh();
+        writer.write_all(&lat.to_le_bytes())?;
+        writer.write_all(&lon.to_le_bytes())?;
+        for micro in dot.shift().to_micros() {
+            writer.write_all(&micro.to_le_bytes())?;
+        }
+    }
+    writer.flush()
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
@@ -0,0 +1,210 @@
+use crate::{Date, Error, Grid, LatLon};
+
+/// 組み込まれた、2011年以降の地震の座標補正パラメータ。発生順。
+/// Embedded corrections for earthquakes after 2011, in chronological order.
+///
+/// 現在は、変換したパラメータをリポジトリに含められる地震がないため、空。
+/// 国土地理院のパラメータファイルは [`GridBuf`](crate::GridBuf) で読み込み、[`Jgd2011::patch_with`](crate::Jgd2011::patch_with) で適用する。
+pub const PATCHES: &[Patch<'static>] = &[];
+
+/// 地震にともなう座標補正。
+/// Coordinate correction for an earthquake.
+///
+/// [`Jgd2011::patch_with`](crate::Jgd2011::patch_with) で、発生日の順に適用される。
+///
+/// # Examples
+///
+/// ```no_run
+/// use jgd::{Date, GridBuf, Patch};
+///
+/// let grid: GridBuf = GridBuf::open("kumamoto2016.par")?;
+/// let patches = [Patch::new("熊本地震", Date::new(2016, 4, 14), grid.as_grid())];
+/// let (lat, lon) = jgd::from_jgd2011(32.8, 130.7)
+///     .patch_with(Date::new(2017, 3, 1), &patches)
+///     .into();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Patch<'a> {
+    name: &'a str,
+    date: Date,
+    grid: Grid<'a>,
+}
+impl<'a> Patch<'a> {
+    /// 地震の名前、発生日、座標補正パラメータから作成する。
+    /// Creates from the name, date and grid of the earthquake.
+    pub const fn new(name: &'a str, date: Date, grid: Grid<'a>) -> Self {
+        Self { name, date, grid }
+    }
+
+    /// 地震の名前。
+    /// Name of the earthquake.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// 地震の発生日。
+    /// Date of the earthquake.
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// 座標補正パラメータ。
+    /// Parameters grid.
+    pub fn grid(&self) -> &Grid<'a> {
+        &self.grid
+    }
+}
+
+/// `epoch` までに発生した地震の補正を、発生順に適用する。
+///
+/// パラメータが存在しない地域では、その地震の補正は行われない。
+pub(crate) fn forward(p: LatLon, epoch: Date, patches: &[Patch]) -> LatLon {
+    sorted(patches, epoch)
+        .into_iter()
+        .fold(p, |p, patch| p + patch.grid.bilinear(p).unwrap_or_default())
+}
+
+/// [`forward`] の逆変換。新しい地震から順に、補正を取り除く。
+pub(crate) fn inverse(p: LatLon, epoch: Date, patches: &[Patch]) -> LatLon {
+    sorted(patches, epoch)
+        .into_iter()
+        .rev()
+        .fold(p, |p, patch| {
+            p - patch.grid.bilinear_inverse(p).unwrap_or_default()
+        })
+}
+
+/// [`inverse`] の収束しない場合をエラーとするもの。
+///
+/// パラメータが存在しない地域では、エラーとせず、その地震の補正は行われない。
+pub(crate) fn try_inverse(p: LatLon, epoch: Date, patches: &[Patch]) -> Result<LatLon, Error> {
+    sorted(patches, epoch)
+        .into_iter()
+        .rev()
+        .try_fold(p, |p, patch| match patch.grid.try_bilinear_inverse(p) {
+            Ok(shift) => Ok(p - shift),
+            Err(Error::OutsideGrid(_)) => Ok(p),
+            Err(e) => Err(e),
+        })
+}
+
+fn sorted<'p, 'a>(patches: &'p [Patch<'a>], epoch: Date) -> Vec<&'p Patch<'a>> {
+    let mut patches = patches
+        .iter()
+        .filter(|patch| patch.date <= epoch)
+        .collect::<Vec<_>>();
+    patches.sort_by_key(|patch| patch.date);
+    patches
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::GridBuf;
+
+    /// 東京付近の1つの3次メッシュで、一様に (秒) ずらす
+    fn uniform(lat: f64, lon: f64) -> GridBuf {
+        let par = format!(
+            "MeshCode   dB(sec)   dL(sec)\n\
+            53394500 {lat} {lon}\n\
+            53394501 {lat} {lon}\n\
+            53394510 {lat} {lon}\n\
+            53394511 {lat} {lon}\n"
+        );
+        GridBuf::from_par(par.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn chronological() {
+        let first = uniform(1., 2.);
+        let second = uniform(-3., 0.5);
+        // 順不同で渡す
+        let patches = [
+            Patch::new("second", Date::new(2024, 1, 1), second.as_grid()),
+            Patch::new("first", Date::new(2016, 4, 14), first.as_grid()),
+        ];
+        let p = LatLon::from_secs(128_410., 502_665.);
+
+        let ret = forward(p, Date::new(2015, 1, 1), &patches);
+        assert_eq!(ret, p);
+
+        let ret = forward(p, Date::new(2016, 4, 14), &patches);
+        assert_abs_diff_eq!(ret.lat(), p.lat() + 1. / 3_600., epsilon = 1e-12);
+        assert_abs_diff_eq!(ret.lon(), p.lon() + 2. / 3_600., epsilon = 1e-12);
+
+        let ret = forward(p, Date::new(2025, 1, 1), &patches);
+        assert_abs_diff_eq!(ret.lat(), p.lat() - 2. / 3_600., epsilon = 1e-12);
+        assert_abs_diff_eq!(ret.lon(), p.lon() + 2.5 / 3_600., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn round_trip() {
+        let first = uniform(1., 2.);
+        let second = uniform(-3., 0.5);
+        let patches = [
+            Patch::new("first", Date::new(2016, 4, 14), first.as_grid()),
+            Patch::new("second", Date::new(2024, 1, 1), second.as_grid()),
+        ];
+        let p = LatLon::from_secs(128_410., 502_665.);
+        let epoch = Date::new(2025, 1, 1);
+        let ret = inverse(forward(p, epoch, &patches), epoch, &patches);
+        assert_abs_diff_eq!(ret.lat(), p.lat(), epsilon = 1e-12);
+        assert_abs_diff_eq!(ret.lon(), p.lon(), epsilon = 1e-12);
+
+        let ret = try_inverse(forward(p, epoch, &patches), epoch, &patches).unwrap();
+        assert_abs_diff_eq!(ret.lat(), p.lat(), epsilon = 1e-12);
+        assert_abs_diff_eq!(ret.lon(), p.lon(), epsilon = 1e-12);
+
+        // パラメータが存在しない地域
+        let outside = LatLon::from_secs(0., 0.);
+        assert_eq!(try_inverse(outside, epoch, &patches), Ok(outside));
+    }
+
+    /// 実際のパラメータとして、平成23年(2011年)東北地方太平洋沖地震のものを用いる
+    #[cfg(feature = "patchjgd")]
+    #[test]
+    fn real_grid() {
+        let tohoku = Patch::new(
+            "平成23年(2011年)東北地方太平洋沖地震",
+            Date::new(2011, 3, 11),
+            crate::TOUHOKUTAIHEIYOUOKI2011,
+        );
+        let p = LatLon::new(38.26, 140.87);
+        let expected = crate::from_jgd2000(p.lat(), p.lon()).to_jgd2011().degrees();
+
+        let patches = [tohoku];
+        assert_eq!(forward(p, Date::new(2011, 3, 10), &patches), p);
+        assert_eq!(forward(p, Date::new(2011, 3, 11), &patches), expected);
+        let ret = inverse(expected, Date::new(2011, 3, 11), &patches);
+        assert_abs_diff_eq!(ret.lat(), p.lat(), epsilon = 1e-9);
+        assert_abs_diff_eq!(ret.lon(), p.lon(), epsilon = 1e-9);
+    }
+
+    #[cfg(feature = "patchjgd")]
+    #[test]
+    fn real_grid_chronological() {
+        let later = uniform(-3., 0.5);
+        // 順不同で渡す
+        let patches = [
+            Patch::new("later", Date::new(2024, 1, 1), later.as_grid()),
+            Patch::new(
+                "平成23年(2011年)東北地方太平洋沖地震",
+                Date::new(2011, 3, 11),
+                crate::TOUHOKUTAIHEIYOUOKI2011,
+            ),
+        ];
+        let p = LatLon::from_secs(128_410., 502_665.);
+        let tohoku = crate::TOUHOKUTAIHEIYOUOKI2011.bilinear(p).unwrap();
+        let shifted = p + tohoku;
+        let expected = shifted + later.bilinear(shifted).unwrap();
+
+        assert_eq!(forward(p, Date::new(2020, 1, 1), &patches), shifted);
+        assert_eq!(forward(p, Date::new(2025, 1, 1), &patches), expected);
+
+        let ret = inverse(expected, Date::new(2025, 1, 1), &patches);
+        assert_abs_diff_eq!(ret.lat(), p.lat(), epsilon = 1e-9);
+        assert_abs_diff_eq!(ret.lon(), p.lon(), epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,231 @@
+use std::marker::PhantomData;
+
+use crate::{crs::Geographic, tm::TransverseMercator, LatLon};
+
+/// 平面直角座標系。
+/// Japan Plane Rectangular Coordinate System.
+///
+/// `T` は基準とする測地系で、[`Tokyo`](crate::Tokyo), [`Jgd2000`](crate::Jgd2000), [`Jgd2011`](crate::Jgd2011) のいずれか。
+///
+/// EPSG: 30161 - 30179 (Tokyo), 2443 - 2461 (JGD2000), 6669 - 6687 (JGD2011)
+///
+/// # Examples
+///
+/// ```
+/// use jgd::{Jgd2011, PlaneRectangular, Zone};
+///
+/// let (x, y) = jgd::from_jgd2011(35.0, 139.0)
+///     .to_plane_rectangular(Zone::IX)
+///     .into();
+///
+/// let (lat, lon) = PlaneRectangular::<Jgd2011>::new(Zone::IX, x, y)
+///     .to_geographic()
+///     .into();
+/// ```
+pub struct PlaneRectangular<T> {
+    zone: Zone,
+    x: f64,
+    y: f64,
+    datum: PhantomData<T>,
+}
+impl<T: Geographic> PlaneRectangular<T> {
+    /// 原点の縮尺係数
+    pub(crate) const SCALE: f64 = 0.9999;
+
+    /// 平面直角座標から変換する。
+    /// Transform from a coordinate in the plane rectangular coordinate system.
+    ///
+    /// `x` は北向き、`y` は東向きの、原点からの距離 (メートル)。
+    pub fn new(zone: Zone, x: f64, y: f64) -> Self {
+        Self {
+            zone,
+            x,
+            y,
+            datum: PhantomData,
+        }
+    }
+
+    pub(crate) fn from_degrees(zone: Zone, degrees: LatLon) -> Self {
+        let (x, y) = Self::projection(zone).forward(degrees);
+        Self::new(zone, x, y)
+    }
+
+    /// 緯度経度へ逆投影する。
+    /// Inverse projection to latitude and longitude.
+    pub fn to_geographic(&self) -> T {
+        T::new(Self::projection(self.zone).inverse(self.x, self.y))
+    }
+
+    /// 系。
+    /// Zone.
+    pub fn zone(&self) -> Zone {
+        self.zone
+    }
+
+    /// 北向きのX座標 (メートル)。
+    /// Northing in meters.
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// 東向きのY座標 (メートル)。
+    /// Easting in meters.
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// EPSGコード。
+    /// EPSG code.
+    pub fn epsg(&self) -> u32 {
+        T::PLANE_RECTANGULAR_EPSG + u32::from(self.zone.number()) - 1
+    }
+
+    fn projection(zone: Zone) -> TransverseMercator {
+        TransverseMercator::new(T::ELLIPSOID, zone.origin(), Self::SCALE, 0.)
+    }
+}
+impl<T> From<PlaneRectangular<T>> for (f64, f64) {
+    /// メートル単位のXとYのペア。
+    /// Northing and easting in meters.
+    fn from(plane: PlaneRectangular<T>) -> Self {
+        (plane.x, plane.y)
+    }
+}
+
+/// 平面直角座標系の系。
+/// Zone of the Japan Plane Rectangular Coordinate System.
+///
+/// 適用区域は国土交通省告示で都道府県ごとに定められている。
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Zone {
+    I = 1,
+    II,
+    III,
+    IV,
+    V,
+    VI,
+    VII,
+    VIII,
+    IX,
+    X,
+    XI,
+    XII,
+    XIII,
+    XIV,
+    XV,
+    XVI,
+    XVII,
+    XVIII,
+    XIX,
+}
+impl Zone {
+    const ALL: [Self; 19] = [
+        Self::I,
+        Self::II,
+        Self::III,
+        Self::IV,
+        Self::V,
+        Self::VI,
+        Self::VII,
+        Self::VIII,
+        Self::IX,
+        Self::X,
+        Self::XI,
+        Self::XII,
+        Self::XIII,
+        Self::XIV,
+        Self::XV,
+        Self::XVI,
+        Self::XVII,
+        Self::XVIII,
+        Self::XIX,
+    ];
+
+    /// 原点の緯度 (度) と経度 (度, 分)
+    const ORIGINS: [(i32, (i32, i32)); 19] = [
+        (33, (129, 30)),
+        (33, (131, 0)),
+        (36, (132, 10)),
+        (33, (133, 30)),
+        (36, (134, 20)),
+        (36, (136, 0)),
+        (36, (137, 10)),
+        (36, (138, 30)),
+        (36, (139, 50)),
+        (40, (140, 50)),
+        (44, (140, 15)),
+        (44, (142, 15)),
+        (44, (144, 15)),
+        (26, (142, 0)),
+        (26, (127, 30)),
+        (26, (124, 0)),
+        (26, (131, 0)),
+        (20, (136, 0)),
+        (26, (154, 0)),
+    ];
+
+    /// 系番号から変換する。
+    /// Converts from the zone number.
+    ///
+    /// 1から19以外は `None` を返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Zone;
+    ///
+    /// assert_eq!(Zone::new(9), Some(Zone::IX));
+    /// assert_eq!(Zone::new(20), None);
+    /// ```
+    pub fn new(number: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(number).checked_sub(1)?).copied()
+    }
+
+    /// 系番号。
+    /// Zone number.
+    pub fn number(self) -> u8 {
+        self as u8
+    }
+
+    /// 原点の緯度経度。
+    /// Latitude and longitude of the origin.
+    pub fn origin(self) -> LatLon {
+        let (lat, (lon, lon_min)) = Self::ORIGINS[usize::from(self.number()) - 1];
+        LatLon::from_dms((lat, 0, 0), (lon, lon_min, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::{Jgd2011, Tokyo};
+
+    #[test]
+    fn zone_number() {
+        for (i, zone) in (1..).zip(Zone::ALL) {
+            assert_eq!(zone.number(), i);
+            assert_eq!(Zone::new(i), Some(zone));
+        }
+        assert_eq!(Zone::new(0), None);
+    }
+
+    #[test]
+    fn origin() {
+        for zone in Zone::ALL {
+            let (x, y) = PlaneRectangular::<Jgd2011>::from_degrees(zone, zone.origin()).into();
+            assert_abs_diff_eq!(x, 0., epsilon = 1e-6);
+            assert_abs_diff_eq!(y, 0., epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn epsg() {
+        let sut = PlaneRectangular::<Tokyo>::new(Zone::I, 0., 0.);
+        assert_eq!(sut.epsg(), 30161);
+        let sut = PlaneRectangular::<Jgd2011>::new(Zone::XIX, 0., 0.);
+        assert_eq!(sut.epsg(), 6687);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::{wkt::Description, Crs};
+
+impl Crs {
+    /// PROJJSON から識別する。
+    /// Identifies from PROJJSON.
+    ///
+    /// [`Crs::from_wkt`] と同じく、EPSG コード、または測地系の名称と投影法のパラメータから識別する。
+    /// 識別できない場合は `None` を返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::Crs;
+    ///
+    /// let json = r#"{"type": "GeographicCRS", "name": "JGD2011", "id": {"authority": "EPSG", "code": 6668}}"#;
+    /// assert_eq!(Crs::from_projjson(json), Crs::from_epsg(6668));
+    /// ```
+    pub fn from_projjson(json: &str) -> Option<Self> {
+        let mut chars = json.chars().peekable();
+        let root = parse(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if chars.next().is_some() {
+            return None;
+        }
+        describe(&root)?.resolve()
+    }
+}
+
+fn describe(root: &Json) -> Option<Description> {
+    let projected = match root.get("type")?.as_str()? {
+        "ProjectedCRS" => true,
+        "GeographicCRS" | "GeodeticCRS" => false,
+        _ => return None,
+    };
+    let id = root
+        .get("id")
+        .or_else(|| root.get("ids")?.as_array()?.first())
+        .filter(|id| id.get("authority").and_then(Json::as_str) == Some("EPSG"));
+    let epsg = id.and_then(|id| match id.get("code")? {
+        Json::Number(code) => Some(*code as u32),
+        Json::String(code) => code.parse().ok(),
+        _ => None,
+    });
+
+    let geographic = root.get("base_crs").unwrap_or(root);
+    let datum = ["datum", "datum_ensemble"]
+        .iter()
+        .find_map(|key| geographic.get(key)?.get("name")?.as_str())
+        .unwrap_or_default();
+    let conversion = root.get("conversion");
+    let method = conversion
+        .and_then(|c| c.get("method")?.get("name")?.as_str())
+        .unwrap_or_default();
+    let parameters = conversion
+        .and_then(|c| c.get("parameters")?.as_array())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| {
+            let name = p.get("name")?.as_str()?;
+            match p.get("value")? {
+                Json::Number(value) => Some((name.to_string(), *value)),
+                _ => None,
+            }
+        })
+        .collect();
+
+    Some(Description {
+        projected,
+        epsg,
+        datum: datum.to_string(),
+        method: method.to_string(),
+        parameters,
+    })
+}
+
+/// JSON の値
+#[derive(Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Self::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+fn parse(chars: &mut Peekable<Chars>) -> Option<Json> {
+    skip_whitespace(chars);
+    let value = match *chars.peek()? {
+        '{' => {
+            chars.next();
+            let mut members = Vec::new();
+            separated(chars, '}', |chars| {
+                skip_whitespace(chars);
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                chars.next_if_eq(&':')?;
+                members.push((key, parse(chars)?));
+                Some(())
+            })?;
+            Json::Object(members)
+        }
+        '[' => {
+            chars.next();
+            let mut values = Vec::new();
+            separated(chars, ']', |chars| {
+                values.push(parse(chars)?);
+                Some(())
+            })?;
+            Json::Array(values)
+        }
+        '"' => Json::String(parse_string(chars)?),
+        _ => {
+            let mut token = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || "+-.".contains(*c)) {
+                token.push(c);
+            }
+            match token.as_str() {
+                "null" => Json::Null,
+                "true" => Json::Bool(true),
+                "false" => Json::Bool(false),
+                number => Json::Number(number.parse().ok()?),
+            }
+        }
+    };
+    skip_whitespace(chars);
+    Some(value)
+}
+
+/// `,` で区切られた要素を `end` まで読む
+fn separated(
+    chars: &mut Peekable<Chars>,
+    end: char,
+    mut element: impl FnMut(&mut Peekable<Chars>) -> Option<()>,
+) -> Option<()> {
+    skip_whitespace(chars);
+    if chars.next_if_eq(&end).is_some() {
+        return Some(());
+    }
+    loop {
+        element(chars)?;
+        match chars.next()? {
+            ',' => continue,
+            c if c == end => return Some(()),
+            _ => return None,
+        }
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    chars.next_if_eq(&'"')?;
+    let mut s = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(s),
+            '\\' => match chars.next()? {
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                'n' => s.push('\n'),
+                'r' => s.push('\r'),
+                't' => s.push('\t'),
+                'u' => {
+                    let hex = (0..4).map(|_| chars.next()).collect::<Option<String>>()?;
+                    // サロゲートペアは識別に用いないため、置換文字とする
+                    let c = u32::from_str_radix(&hex, 16).ok()?;
+                    s.push(char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                c => s.push(c),
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// PROJ が出力する PROJJSON から、一部を省略したもの
+    const JGD2000_IX: &str = r#"{
+  "$schema": "https://proj.org/schemas/v0.7/projjson.schema.json",
+  "type": "ProjectedCRS",
+  "name": "JGD2000 / Japan Plane Rectangular CS IX",
+  "base_crs": {
+    "name": "JGD2000",
+    "datum": {
+      "type": "GeodeticReferenceFrame",
+      "name": "Japanese Geodetic Datum 2000",
+      "ellipsoid": {
+        "name": "GRS 1980",
+        "semi_major_axis": 6378137,
+        "inverse_flattening": 298.257222101
+      }
+    },
+    "id": {"authority": "EPSG", "code": 4612}
+  },
+  "conversion": {
+    "name": "Japan Plane Rectangular CS zone IX",
+    "method": {
+      "name": "Transverse Mercator",
+      "id": {"authority": "EPSG", "code": 9807}
+    },
+    "parameters": [
+      {"name": "Latitude of natural origin", "value": 36, "unit": "degree"},
+      {"name": "Longitude of natural origin", "value": 139.833333333333, "unit": "degree"},
+      {"name": "Scale factor at natural origin", "value": 0.9999, "unit": "unity"},
+      {"name": "False easting", "value": 0, "unit": "metre"},
+      {"name": "False northing", "value": 0, "unit": "metre"}
+    ]
+  },
+  "scope": "Cadastre, engineering survey, topographic mapping.",
+  "bbox": {"south_latitude": 35.64, "west_longitude": 138.38, "north_latitude": 37.98, "east_longitude": 141.11},
+  "id": {"authority": "EPSG", "code": 2451}
+}"#;
+
+    #[test]
+    fn epsg() {
+        assert_eq!(Crs::from_projjson(JGD2000_IX), Crs::from_epsg(2451));
+    }
+
+    #[test]
+    fn parameters() {
+        let json = JGD2000_IX.replace(r#""code": 2451"#, r#""code": 1"#);
+        assert_eq!(Crs::from_projjson(&json), Crs::from_epsg(2451));
+
+        let json = json.replace("139.833333333333", "141");
+        assert_eq!(Crs::from_projjson(&json), None);
+    }
+
+    #[test]
+    fn json() {
+        let mut chars = r#" {"a": [1, -2.5e1, true, null], "b\"A": {}} "#.chars().peekable();
+        let ret = parse(&mut chars).unwrap();
+        assert_eq!(
+            ret,
+            Json::Object(vec![
+                (
+                    "a".to_string(),
+                    Json::Array(vec![
+                        Json::Number(1.),
+                        Json::Number(-25.),
+                        Json::Bool(true),
+                        Json::Null
+                    ])
+                ),
+                ("b\"A".to_string(), Json::Object(vec![])),
+            ])
+        );
+        assert!(Crs::from_projjson(r#"{"type": "ProjectedCRS""#).is_none());
+        assert!(Crs::from_projjson("[]").is_none());
+    }
+}
@@ -0,0 +1,143 @@
+use std::{
+    io::{self, Read},
+    path::Path,
+};
+
+use crate::{Displacement, Grid, GridBuf};
+
+/// セミ・ダイナミック補正パラメータ。
+/// Semi-dynamic correction parameters.
+///
+/// 国土地理院が年度ごとに公開する `SemiDyna20xx.par` を、実行時にファイルから読み込む。
+/// 元期 (2011年) から今期までの地殻変動量を、2次メッシュ (5分 × 7.5分) の格子点ごとに持つ。
+///
+/// 観測した年度のパラメータを用いて、[`Jgd2011::to_reference_epoch`](crate::Jgd2011::to_reference_epoch) で今期から元期へ、
+/// [`Jgd2011::from_reference_epoch`](crate::Jgd2011::from_reference_epoch) で元期から今期へ変換する。
+///
+/// # Examples
+///
+/// ```no_run
+/// use jgd::SemiDyna;
+///
+/// let semidyna = SemiDyna::open("SemiDyna2024.par")?;
+/// let (lat, lon, height) = jgd::from_jgd2011_3d(35.0, 135.0, 100.0)
+///     .to_reference_epoch(&semidyna)
+///     .into();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SemiDyna {
+    grid: GridBuf<Displacement>,
+}
+impl SemiDyna {
+    /// 格子の間隔: 2次メッシュ = 3次メッシュ10個分
+    const STEP: i16 = 10;
+
+    /// ファイルから読み込む。
+    /// Loads from a file.
+    ///
+    /// 拡張子が `.in` であれば `par/conv` で変換した形式、それ以外は `.par` 形式として読み込む。
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(GridBuf::open(path)?)
+    }
+
+    /// 国土地理院の `.par` 形式から読み込む。
+    /// Parses the `.par` format of GSI.
+    ///
+    /// 列は `MeshCode dB(sec) dL(sec) dH(m)` で、格子点は2次メッシュの南西隅でなければならない。
+    pub fn from_par(reader: impl Read) -> io::Result<Self> {
+        Self::new(GridBuf::from_par(reader)?)
+    }
+
+    /// パラメータグリッド。
+    /// Parameters grid.
+    pub fn grid(&self) -> Grid<'_, Displacement> {
+        self.grid.as_grid()
+    }
+
+    fn new(grid: GridBuf<Displacement>) -> io::Result<Self> {
+        let grid = grid.with_step(Self::STEP).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "grid points must be at the 2nd mesh",
+            )
+        })?;
+        Ok(Self { grid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::{Error, Jgd2011, LatLon, LatLonHeight};
+
+    /// 東京付近の1つの2次メッシュ
+    const PAR: &str = "\
+SemiDyna test\r
+MeshCode dB(sec)  dL(sec) dH(m)\r
+53394500  -0.06000   0.00000   0.000\r
+53394600   0.00000   0.06000   0.030\r
+53395500   0.00000   0.00000   0.000\r
+53395600   0.06000   0.06000   0.030\r
+";
+
+    /// 2次メッシュの南西端から北へ100秒、東へ150秒
+    fn reference() -> LatLonHeight {
+        LatLonHeight::new(LatLon::from_secs(128_500., 502_800.), 10.)
+    }
+
+    #[test]
+    fn bilinear() {
+        let sut = SemiDyna::from_par(PAR.as_bytes()).unwrap();
+        let ret = sut.grid().bilinear(reference().lat_lon()).unwrap();
+        assert_abs_diff_eq!(ret.lat_lon().lat(), -0.02 / 3_600., epsilon = 1e-12);
+        assert_abs_diff_eq!(ret.lat_lon().lon(), 0.02 / 3_600., epsilon = 1e-12);
+        assert_abs_diff_eq!(ret.height(), 0.01, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn round_trip() {
+        let sut = SemiDyna::from_par(PAR.as_bytes()).unwrap();
+        let current = Jgd2011::new_3d(reference()).from_reference_epoch(&sut);
+        assert_abs_diff_eq!(current.height(), 10.01, epsilon = 1e-9);
+
+        let ret = current.to_reference_epoch(&sut);
+        assert_abs_diff_eq!(
+            ret.degrees().lat(),
+            reference().lat_lon().lat(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            ret.degrees().lon(),
+            reference().lat_lon().lon(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(ret.height(), 10., epsilon = 1e-6);
+    }
+
+    #[test]
+    fn outside() {
+        let sut = SemiDyna::from_par(PAR.as_bytes()).unwrap();
+        let p = LatLon::from_secs(0., 0.);
+        let current = Jgd2011::new(p);
+        assert_eq!(
+            current.try_to_reference_epoch(&sut).unwrap_err(),
+            Error::OutsideGrid(p)
+        );
+        assert_eq!(
+            current.try_from_reference_epoch(&sut).unwrap_err(),
+            Error::OutsideGrid(p)
+        );
+        assert!(Jgd2011::new_3d(reference())
+            .try_from_reference_epoch(&sut)
+            .is_ok());
+    }
+
+    #[test]
+    fn not_2nd_mesh() {
+        let par = PAR.replace("53394500", "53394501");
+        assert!(SemiDyna::from_par(par.as_bytes()).is_err());
+    }
+}
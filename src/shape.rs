@@ -0,0 +1,164 @@
+use crate::{Interpolation, Method};
+
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+use crate::Jgd2000;
+
+#[cfg(feature = "tky2jgd")]
+use crate::Tokyo;
+
+#[cfg(feature = "patchjgd")]
+use crate::Jgd2011;
+
+/// 形状全体に一貫して適用する変換方法。
+/// Strategy applied consistently to all points of a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// すべての点をパラメータグリッドで変換する。
+    /// Transform all points with the parameters grid.
+    ///
+    /// 四隅の一部が欠けた点は、指定された方法で補間する。
+    /// それでも補間できない点が一つでもあれば、すべての点をフォールバックする。
+    Grid(Interpolation),
+
+    /// パラメータグリッドを用いず、すべての点をフォールバックする。
+    /// Fall back all points without the parameters grid.
+    Fallback,
+}
+impl Default for Strategy {
+    fn default() -> Self {
+        Self::Grid(Interpolation::Bilinear)
+    }
+}
+
+/// 一貫した方法で変換された形状。
+/// Shape transformed in a consistent way.
+///
+/// 複数の座標で表される形状の一部だけがパラメータグリッドの範囲外にあると、点ごとに異なる方法で変換され、形状が大きく変わる可能性がある。
+/// 形状全体に一つの [`Strategy`] を適用することで、これを避ける。
+///
+/// # Examples
+///
+/// ```
+/// use jgd::{Interpolation, Shape, Strategy};
+///
+/// # #[cfg(feature = "tky2jgd")]
+/// # {
+/// let parcel = [
+///     jgd::from_tokyo(35.0, 135.0),
+///     jgd::from_tokyo(35.0, 135.001),
+///     jgd::from_tokyo(35.001, 135.001),
+/// ];
+/// let shape = Shape::from_tokyo(&parcel, Strategy::Grid(Interpolation::Partial));
+/// assert!(!shape.straddles());
+/// let jgd2000 = shape.points();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Shape<T> {
+    points: Vec<T>,
+    method: Method,
+    straddles: bool,
+}
+impl<T> Shape<T> {
+    /// 変換後の点。
+    /// Transformed points.
+    pub fn points(&self) -> &[T] {
+        &self.points
+    }
+
+    /// 変換後の点。
+    /// Transformed points.
+    pub fn into_points(self) -> Vec<T> {
+        self.points
+    }
+
+    /// 形状全体に適用された方法。
+    /// Method applied to the shape.
+    ///
+    /// パラメータグリッドによる場合は、点ごとの方法のうち最も精度の低いもの。
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// 形状がパラメータグリッドの範囲内外をまたがっているかどうか。
+    /// Whether the shape straddles the edge of the parameters grid.
+    ///
+    /// 四隅すべてのパラメータが存在する点と、存在しない点が混在していれば `true`。
+    /// 適用された [`Strategy`] によらない。
+    pub fn straddles(&self) -> bool {
+        self.straddles
+    }
+}
+#[cfg(feature = "tky2jgd")]
+impl Shape<Jgd2000> {
+    /// [`Tokyo`] の形状を [`Jgd2000`] へ変換する。
+    /// Transform a shape in Tokyo Datum to JGD2000.
+    ///
+    /// フォールバックは [`Tokyo::to_jgd2000`] と同じく、[`Tokyo97`](crate::Tokyo97) を経由した3パラメータによる変換。
+    pub fn from_tokyo(shape: &[Tokyo], strategy: Strategy) -> Self {
+        transform(
+            shape,
+            strategy,
+            |p, interpolation| p.to_jgd2000_with_method(interpolation),
+            |p| p.to_tokyo97().to_jgd2000(),
+            Method::ThreeParameter,
+        )
+    }
+}
+#[cfg(feature = "patchjgd")]
+impl Shape<Jgd2011> {
+    /// [`Jgd2000`] の形状を [`Jgd2011`] へ変換する。
+    /// Transform a shape in JGD2000 to JGD2011.
+    ///
+    /// フォールバックは [`Jgd2000::to_jgd2011`] の範囲外と同じく、何も行わない。
+    pub fn from_jgd2000(shape: &[Jgd2000], strategy: Strategy) -> Self {
+        transform(
+            shape,
+            strategy,
+            |p, interpolation| p.to_jgd2011_with_method(interpolation),
+            |p| Jgd2011::new(p.degrees()),
+            Method::Identity,
+        )
+    }
+}
+
+#[cfg(any(feature = "tky2jgd", feature = "patchjgd"))]
+fn transform<S, T>(
+    shape: &[S],
+    strategy: Strategy,
+    with_method: impl Fn(&S, Interpolation) -> (T, Method),
+    fallback: impl Fn(&S) -> T,
+    fallback_method: Method,
+) -> Shape<T> {
+    let inside = shape
+        .iter()
+        .filter(|p| with_method(p, Interpolation::Bilinear).1 == Method::Bilinear)
+        .count();
+    let straddles = 0 < inside && inside < shape.len();
+
+    if let Strategy::Grid(interpolation) = strategy {
+        let transformed = shape
+            .iter()
+            .map(|p| with_method(p, interpolation))
+            .collect::<Vec<_>>();
+        if transformed.iter().all(|(_, method)| method.is_grid()) {
+            let method = transformed
+                .iter()
+                .map(|(_, method)| *method)
+                .reduce(Method::coarser)
+                .unwrap_or(Method::Bilinear);
+            let points = transformed.into_iter().map(|(p, _)| p).collect();
+            return Shape {
+                points,
+                method,
+                straddles,
+            };
+        }
+    }
+
+    Shape {
+        points: shape.iter().map(fallback).collect(),
+        method: fallback_method,
+        straddles,
+    }
+}
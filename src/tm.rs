@@ -0,0 +1,143 @@
+use crate::{earth::Ellipsoid, LatLon};
+
+/// 横メルカトル図法 (ガウス・クリューゲル図法)。
+/// Transverse Mercator projection.
+///
+/// 国土地理院の計算式 [(河瀬, 2011)](crate#references) による。
+// https://vldb.gsi.go.jp/sokuchi/surveycalc/surveycalc/algorithm/xy2bl/xy2bl.htm
+#[derive(Debug, Clone)]
+pub struct TransverseMercator {
+    ellipsoid: Ellipsoid,
+
+    /// 原点の緯度経度
+    origin: LatLon,
+
+    /// 原点の縮尺係数
+    scale: f64,
+
+    /// 原点の横座標 (メートル)
+    false_easting: f64,
+}
+impl TransverseMercator {
+    pub const fn new(ellipsoid: Ellipsoid, origin: LatLon, scale: f64, false_easting: f64) -> Self {
+        Self {
+            ellipsoid,
+            origin,
+            scale,
+            false_easting,
+        }
+    }
+
+    /// 緯度経度から、北向きの縦座標Xと東向きの横座標Y (メートル) に投影する。
+    pub fn forward(&self, degrees: LatLon) -> (f64, f64) {
+        let n = self.ellipsoid.third_flattening();
+        let alpha = alpha(n);
+        let (lat, lon) = degrees.map(f64::to_radians).into();
+        let lon0 = self.origin.lon().to_radians();
+
+        let e = 2. * n.sqrt() / (1. + n);
+        let t = (lat.sin().atanh() - e * (e * lat.sin()).atanh()).sinh();
+        let t_bar = (1. + t * t).sqrt();
+        let xi = t.atan2((lon - lon0).cos());
+        let eta = ((lon - lon0).sin() / t_bar).atanh();
+
+        let (mut x, mut y) = (xi, eta);
+        for (j, a) in (1..).zip(alpha) {
+            let j = f64::from(j) * 2.;
+            x += a * (j * xi).sin() * (j * eta).cosh();
+            y += a * (j * xi).cos() * (j * eta).sinh();
+        }
+
+        let a_bar = self.a_bar(n);
+        (
+            a_bar * x - self.meridian_arc(n),
+            a_bar * y + self.false_easting,
+        )
+    }
+
+    /// 北向きの縦座標Xと東向きの横座標Y (メートル) から、緯度経度に逆投影する。
+    pub fn inverse(&self, x: f64, y: f64) -> LatLon {
+        let n = self.ellipsoid.third_flattening();
+        let a_bar = self.a_bar(n);
+        let xi = (x + self.meridian_arc(n)) / a_bar;
+        let eta = (y - self.false_easting) / a_bar;
+
+        let (mut xi2, mut eta2) = (xi, eta);
+        for (j, b) in (1..).zip(beta(n)) {
+            let j = f64::from(j) * 2.;
+            xi2 -= b * (j * xi).sin() * (j * eta).cosh();
+            eta2 -= b * (j * xi).cos() * (j * eta).sinh();
+        }
+
+        let chi = (xi2.sin() / eta2.cosh()).asin();
+        let mut lat = chi;
+        for (j, d) in (1..).zip(delta(n)) {
+            lat += d * (f64::from(j) * 2. * chi).sin();
+        }
+        let lon = self.origin.lon().to_radians() + eta2.sinh().atan2(xi2.cos());
+
+        LatLon::new(lat.to_degrees(), lon.to_degrees())
+    }
+
+    /// 縮尺を乗じた、赤道から原点までの子午線弧長 (メートル)
+    fn meridian_arc(&self, n: f64) -> f64 {
+        let lat0 = self.origin.lat().to_radians();
+        let a = big_a(n);
+        let mut s = a[0] * lat0;
+        for (j, a) in (1..).zip(&a[1..]) {
+            s += a * (f64::from(j) * 2. * lat0).sin();
+        }
+        self.scale * self.ellipsoid.equatorial_radius() / (1. + n) * s
+    }
+
+    /// 縮尺を乗じた、子午線弧長の係数 (メートル)
+    fn a_bar(&self, n: f64) -> f64 {
+        self.scale * self.ellipsoid.equatorial_radius() / (1. + n) * big_a(n)[0]
+    }
+}
+
+fn big_a(n: f64) -> [f64; 6] {
+    let [n2, n3, n4, n5] = [2, 3, 4, 5].map(|i| n.powi(i));
+    [
+        1. + n2 / 4. + n4 / 64.,
+        -3. / 2. * (n - n3 / 8. - n5 / 64.),
+        15. / 16. * (n2 - n4 / 4.),
+        -35. / 48. * (n3 - 5. / 16. * n5),
+        315. / 512. * n4,
+        -693. / 1280. * n5,
+    ]
+}
+
+fn alpha(n: f64) -> [f64; 5] {
+    let [n2, n3, n4, n5] = [2, 3, 4, 5].map(|i| n.powi(i));
+    [
+        n / 2. - 2. / 3. * n2 + 5. / 16. * n3 + 41. / 180. * n4 - 127. / 288. * n5,
+        13. / 48. * n2 - 3. / 5. * n3 + 557. / 1440. * n4 + 281. / 630. * n5,
+        61. / 240. * n3 - 103. / 140. * n4 + 15061. / 26880. * n5,
+        49561. / 161280. * n4 - 179. / 168. * n5,
+        34729. / 80640. * n5,
+    ]
+}
+
+fn beta(n: f64) -> [f64; 5] {
+    let [n2, n3, n4, n5] = [2, 3, 4, 5].map(|i| n.powi(i));
+    [
+        n / 2. - 2. / 3. * n2 + 37. / 96. * n3 - 1. / 360. * n4 - 81. / 512. * n5,
+        1. / 48. * n2 + 1. / 15. * n3 - 437. / 1440. * n4 + 46. / 105. * n5,
+        17. / 480. * n3 - 37. / 840. * n4 - 209. / 4480. * n5,
+        4397. / 161280. * n4 - 11. / 504. * n5,
+        4583. / 161280. * n5,
+    ]
+}
+
+fn delta(n: f64) -> [f64; 6] {
+    let [n2, n3, n4, n5, n6] = [2, 3, 4, 5, 6].map(|i| n.powi(i));
+    [
+        2. * n - 2. / 3. * n2 - 2. * n3 + 116. / 45. * n4 + 26. / 45. * n5 - 2854. / 675. * n6,
+        7. / 3. * n2 - 8. / 5. * n3 - 227. / 45. * n4 + 2704. / 315. * n5 + 2323. / 945. * n6,
+        56. / 15. * n3 - 136. / 35. * n4 - 1262. / 105. * n5 + 73814. / 2835. * n6,
+        4279. / 630. * n4 - 332. / 35. * n5 - 399572. / 14175. * n6,
+        4174. / 315. * n5 - 144838. / 6237. * n6,
+        601676. / 22275. * n6,
+    ]
+}
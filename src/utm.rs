@@ -0,0 +1,143 @@
+use std::marker::PhantomData;
+
+use crate::{crs::Geographic, tm::TransverseMercator, LatLon};
+
+/// UTM座標系。
+/// Universal Transverse Mercator coordinate system.
+///
+/// `T` は基準とする測地系で、[`Jgd2000`](crate::Jgd2000), [`Jgd2011`](crate::Jgd2011) のいずれか。
+/// 北半球のみを対象とし、日本の国土は51帯から56帯に含まれる。
+///
+/// EPSG: 3097 - 3101 (JGD2000), 6688 - 6692 (JGD2011)
+///
+/// # Examples
+///
+/// ```
+/// use jgd::{Jgd2011, Utm};
+///
+/// let utm = jgd::from_jgd2011(35.0, 139.0).to_utm();
+/// assert_eq!(utm.zone(), 54);
+///
+/// let (lat, lon) = Utm::<Jgd2011>::new(54, utm.easting(), utm.northing())
+///     .to_geographic()
+///     .into();
+/// ```
+pub struct Utm<T> {
+    zone: u8,
+    easting: f64,
+    northing: f64,
+    datum: PhantomData<T>,
+}
+impl<T: Geographic> Utm<T> {
+    /// 中央子午線の縮尺係数
+    pub(crate) const SCALE: f64 = 0.9996;
+
+    /// 中央子午線の横座標 (メートル)
+    pub(crate) const FALSE_EASTING: f64 = 500_000.;
+
+    /// UTM座標から変換する。
+    /// Transform from a coordinate in UTM.
+    ///
+    /// # Panics
+    ///
+    /// 帯番号が1から60以外の場合。
+    pub fn new(zone: u8, easting: f64, northing: f64) -> Self {
+        assert!((1..=60).contains(&zone), "zone must be in 1..=60");
+        Self {
+            zone,
+            easting,
+            northing,
+            datum: PhantomData,
+        }
+    }
+
+    pub(crate) fn from_degrees(degrees: LatLon) -> Self {
+        Self::from_degrees_in(Self::zone_of(degrees), degrees)
+    }
+
+    pub(crate) fn from_degrees_in(zone: u8, degrees: LatLon) -> Self {
+        let (northing, easting) = Self::projection(zone).forward(degrees);
+        Self::new(zone, easting, northing)
+    }
+
+    /// 緯度経度へ逆投影する。
+    /// Inverse projection to latitude and longitude.
+    pub fn to_geographic(&self) -> T {
+        let degrees = Self::projection(self.zone).inverse(self.northing, self.easting);
+        T::new(degrees)
+    }
+
+    /// 帯番号。
+    /// Zone number.
+    pub fn zone(&self) -> u8 {
+        self.zone
+    }
+
+    /// 東向きの座標 (メートル)。
+    /// Easting in meters.
+    pub fn easting(&self) -> f64 {
+        self.easting
+    }
+
+    /// 北向きの座標 (メートル)。
+    /// Northing in meters.
+    pub fn northing(&self) -> f64 {
+        self.northing
+    }
+
+    /// EPSGコード。
+    /// EPSG code.
+    ///
+    /// 登録されていない帯では `None` を返す。
+    pub fn epsg(&self) -> Option<u32> {
+        let zone51 = T::UTM_EPSG?;
+        (51..=55)
+            .contains(&self.zone)
+            .then(|| zone51 + u32::from(self.zone - 51))
+    }
+
+    /// 経度から帯番号を求める
+    fn zone_of(degrees: LatLon) -> u8 {
+        let zone = ((degrees.lon() + 180.) / 6.).floor() as i32 + 1;
+        zone.clamp(1, 60) as u8
+    }
+
+    fn projection(zone: u8) -> TransverseMercator {
+        let lon0 = f64::from(zone) * 6. - 183.;
+        let origin = LatLon::new(0., lon0);
+        TransverseMercator::new(T::ELLIPSOID, origin, Self::SCALE, Self::FALSE_EASTING)
+    }
+}
+impl<T> From<Utm<T>> for (f64, f64) {
+    /// メートル単位の東向きと北向きの座標のペア。
+    /// Easting and northing in meters.
+    fn from(utm: Utm<T>) -> Self {
+        (utm.easting, utm.northing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Jgd2000, Jgd2011};
+
+    #[test]
+    fn zone() {
+        let zone = |lon| Utm::<Jgd2011>::zone_of(LatLon::new(35., lon));
+        assert_eq!(zone(122.94), 51);
+        assert_eq!(zone(126.0), 52);
+        assert_eq!(zone(139.77), 54);
+        assert_eq!(zone(153.98), 56);
+        assert_eq!(zone(-180.0), 1);
+        assert_eq!(zone(180.0), 60);
+    }
+
+    #[test]
+    fn epsg() {
+        assert_eq!(Utm::<Jgd2011>::new(51, 0., 0.).epsg(), Some(6688));
+        assert_eq!(Utm::<Jgd2011>::new(55, 0., 0.).epsg(), Some(6692));
+        assert_eq!(Utm::<Jgd2000>::new(54, 0., 0.).epsg(), Some(3100));
+        assert_eq!(Utm::<Jgd2000>::new(56, 0., 0.).epsg(), None);
+        assert_eq!(Utm::<Jgd2000>::new(50, 0., 0.).epsg(), None);
+    }
+}
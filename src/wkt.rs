@@ -0,0 +1,595 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::{
+    epsg::Base, Crs, Datum, Jgd2011, PlaneRectangular, Projection, Utm, WebMercator, Zone,
+};
+
+/// 度の単位のラジアン
+const DEGREE: &str = "0.0174532925199433";
+
+impl Crs {
+    /// ESRI の `.prj` 形式の WKT1 で表す。
+    /// Describes in WKT1 of the ESRI `.prj` flavour.
+    ///
+    /// ESRI の形式には EPSG コードが含まれないため、[`Crs::from_wkt`] は名称とパラメータから識別する。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let prj = jgd::Crs::from_epsg(6668).unwrap().to_wkt1();
+    /// assert!(prj.starts_with(r#"GEOGCS["GCS_JGD_2011""#));
+    /// ```
+    pub fn to_wkt1(&self) -> String {
+        let geog = self.geog();
+        let geogcs = format!(
+            r#"GEOGCS["{}",DATUM["{}",SPHEROID["{}",{:?},{:?}]],PRIMEM["Greenwich",0.0],UNIT["Degree",{DEGREE}]]"#,
+            geog.esri[0], geog.esri[1], geog.esri[2], geog.semi_major, geog.inverse_flattening
+        );
+        let prefix = geog.esri_prefix;
+        let (name, projection, parameters) = match self.projection() {
+            Projection::Geographic => return geogcs,
+            Projection::WebMercator => (
+                format!("{prefix}Web_Mercator_Auxiliary_Sphere"),
+                "Mercator_Auxiliary_Sphere",
+                [
+                    ("False_Easting", 0.),
+                    ("False_Northing", 0.),
+                    ("Central_Meridian", 0.),
+                    ("Standard_Parallel_1", 0.),
+                    ("Auxiliary_Sphere_Type", 0.),
+                ],
+            ),
+            projection => {
+                let tm = TransverseMercator::new(projection);
+                let name = match projection {
+                    Projection::Utm(zone) => format!("{prefix}UTM_Zone_{zone}N"),
+                    _ => format!("{prefix}Japan_Zone_{}", tm.zone_number),
+                };
+                (
+                    name,
+                    "Transverse_Mercator",
+                    [
+                        ("False_Easting", tm.false_easting),
+                        ("False_Northing", 0.),
+                        ("Central_Meridian", tm.lon0),
+                        ("Scale_Factor", tm.scale),
+                        ("Latitude_Of_Origin", tm.lat0),
+                    ],
+                )
+            }
+        };
+        let parameters = parameters
+            .iter()
+            .map(|(name, value)| format!(r#"PARAMETER["{name}",{value:?}],"#))
+            .collect::<String>();
+        format!(
+            r#"PROJCS["{name}",{geogcs},PROJECTION["{projection}"],{parameters}UNIT["Meter",1.0]]"#
+        )
+    }
+
+    /// WKT2 (ISO 19162:2019) で表す。
+    /// Describes in WKT2 (ISO 19162:2019).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let wkt = jgd::Crs::from_epsg(6677).unwrap().to_wkt2();
+    /// assert!(wkt.starts_with(r#"PROJCRS["JGD2011 / Japan Plane Rectangular CS IX""#));
+    /// ```
+    pub fn to_wkt2(&self) -> String {
+        let geog = self.geog();
+        let degree = format!(r#"ANGLEUNIT["degree",{DEGREE}]"#);
+        let metre = r#"LENGTHUNIT["metre",1]"#;
+        let datum = format!(
+            r#"DATUM["{}",ELLIPSOID["{}",{},{},{metre}]],PRIMEM["Greenwich",0,{degree}]"#,
+            geog.datum, geog.ellipsoid, geog.semi_major, geog.inverse_flattening
+        );
+        let id = format!(r#"ID["EPSG",{}]"#, self.epsg());
+        let axis = |order: usize, (name, direction): (&str, &str), unit: &str| {
+            format!(r#"AXIS["{name}",{direction},ORDER[{order}],{unit}]"#)
+        };
+
+        let parameter = |name: &str, value: f64, unit: &str, id: u32| {
+            format!(r#"PARAMETER["{name}",{value},{unit},ID["EPSG",{id}]]"#)
+        };
+        let origin = |lat0: f64, lon0: f64| {
+            [
+                parameter("Latitude of natural origin", lat0, &degree, 8801),
+                parameter("Longitude of natural origin", lon0, &degree, 8802),
+            ]
+        };
+        let false_origin = |false_easting: f64| {
+            [
+                parameter("False easting", false_easting, metre, 8806),
+                parameter("False northing", 0., metre, 8807),
+            ]
+        };
+
+        let (name, conversion, method, parameters, axes) = match self.projection() {
+            Projection::Geographic => {
+                return format!(
+                    r#"GEOGCRS["{}",{datum},CS[ellipsoidal,2],{},{},{id}]"#,
+                    geog.name,
+                    axis(1, ("geodetic latitude (Lat)", "north"), &degree),
+                    axis(2, ("geodetic longitude (Lon)", "east"), &degree),
+                );
+            }
+            Projection::WebMercator => (
+                format!("{} / Pseudo-Mercator", geog.name),
+                "Popular Visualisation Pseudo-Mercator".to_string(),
+                r#"METHOD["Popular Visualisation Pseudo Mercator",ID["EPSG",1024]]"#,
+                [origin(0., 0.), false_origin(0.)].concat(),
+                [("easting (X)", "east"), ("northing (Y)", "north")],
+            ),
+            projection => {
+                let tm = TransverseMercator::new(projection);
+                let (name, conversion, axes) = match projection {
+                    Projection::Utm(zone) => (
+                        format!("{} / UTM zone {zone}N", geog.name),
+                        format!("UTM zone {zone}N"),
+                        [("(E)", "east"), ("(N)", "north")],
+                    ),
+                    _ => (
+                        format!("{} / Japan Plane Rectangular CS {}", geog.name, tm.zone),
+                        format!("Japan Plane Rectangular CS zone {}", tm.zone),
+                        [("northing (X)", "north"), ("easting (Y)", "east")],
+                    ),
+                };
+                let scale = format!(
+                    r#"PARAMETER["Scale factor at natural origin",{},SCALEUNIT["unity",1],ID["EPSG",8805]]"#,
+                    tm.scale
+                );
+                (
+                    name,
+                    conversion,
+                    r#"METHOD["Transverse Mercator",ID["EPSG",9807]]"#,
+                    [
+                        &origin(tm.lat0, tm.lon0)[..],
+                        &[scale],
+                        &false_origin(tm.false_easting),
+                    ]
+                    .concat(),
+                    axes,
+                )
+            }
+        };
+
+        format!(
+            r#"PROJCRS["{name}",BASEGEOGCRS["{}",{datum}],CONVERSION["{conversion}",{method},{}],CS[Cartesian,2],{},{},{id}]"#,
+            geog.name,
+            parameters.join(","),
+            axis(1, axes[0], metre),
+            axis(2, axes[1], metre),
+        )
+    }
+
+    /// WKT1 または WKT2 から識別する。
+    /// Identifies from WKT1 or WKT2.
+    ///
+    /// EPSG コードが含まれていれば、それを用いる。
+    /// 含まれていなければ、測地系の名称と、投影法のパラメータから識別する。
+    /// 識別できない場合は `None` を返す。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use jgd::{Crs, Datum};
+    ///
+    /// let prj = r#"GEOGCS["GCS_Tokyo",DATUM["D_Tokyo",SPHEROID["Bessel_1841",6377397.155,299.1528128]],PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]]"#;
+    /// let crs = Crs::from_wkt(prj).unwrap();
+    /// assert_eq!(crs.datum(), Datum::Tokyo);
+    /// ```
+    pub fn from_wkt(wkt: &str) -> Option<Self> {
+        let mut chars = wkt.trim().chars().peekable();
+        let root = match parse_arg(&mut chars)? {
+            Arg::Node(node) => node,
+            Arg::Text(_) => return None,
+        };
+        if chars.next().is_some() {
+            return None;
+        }
+        Description::from_wkt(&root)?.resolve()
+    }
+
+    fn geog(&self) -> &'static Geog {
+        match (self.projection(), self.base()) {
+            (Projection::WebMercator, _) => &WGS84,
+            (_, Base::Tokyo) => &TOKYO,
+            (_, Base::Jgd2000) => &JGD2000,
+            (_, Base::Jgd2011) => &JGD2011,
+        }
+    }
+}
+
+/// 地理座標系の名称と楕円体
+struct Geog {
+    /// EPSG の名称
+    name: &'static str,
+    datum: &'static str,
+    ellipsoid: &'static str,
+
+    /// ESRI の地理座標系、測地系、楕円体の名称
+    esri: [&'static str; 3],
+
+    /// ESRI の投影座標系の名称の接頭辞
+    esri_prefix: &'static str,
+
+    semi_major: f64,
+    inverse_flattening: f64,
+}
+const TOKYO: Geog = Geog {
+    name: "Tokyo",
+    datum: "Tokyo",
+    ellipsoid: "Bessel 1841",
+    esri: ["GCS_Tokyo", "D_Tokyo", "Bessel_1841"],
+    esri_prefix: "",
+    semi_major: 6377397.155,
+    inverse_flattening: 299.1528128,
+};
+const JGD2000: Geog = Geog {
+    name: "JGD2000",
+    datum: "Japanese Geodetic Datum 2000",
+    ellipsoid: "GRS 1980",
+    esri: ["GCS_JGD_2000", "D_JGD_2000", "GRS_1980"],
+    esri_prefix: "JGD_2000_",
+    semi_major: 6378137.,
+    inverse_flattening: 298.257222101,
+};
+const JGD2011: Geog = Geog {
+    name: "JGD2011",
+    datum: "Japanese Geodetic Datum 2011",
+    ellipsoid: "GRS 1980",
+    esri: ["GCS_JGD_2011", "D_JGD_2011", "GRS_1980"],
+    esri_prefix: "JGD_2011_",
+    semi_major: 6378137.,
+    inverse_flattening: 298.257222101,
+};
+/// [`WebMercator`] の本来の基準
+const WGS84: Geog = Geog {
+    name: "WGS 84",
+    datum: "World Geodetic System 1984",
+    ellipsoid: "WGS 84",
+    esri: ["GCS_WGS_1984", "D_WGS_1984", "WGS_1984"],
+    esri_prefix: "WGS_1984_",
+    semi_major: 6378137.,
+    inverse_flattening: 298.257223563,
+};
+
+/// 横メルカトル図法のパラメータ
+struct TransverseMercator {
+    zone: String,
+    zone_number: u8,
+    lat0: f64,
+    lon0: f64,
+    scale: f64,
+    false_easting: f64,
+}
+impl TransverseMercator {
+    fn new(projection: Projection) -> Self {
+        match projection {
+            Projection::Utm(zone) => Self {
+                zone: zone.to_string(),
+                zone_number: zone,
+                lat0: 0.,
+                lon0: f64::from(zone) * 6. - 183.,
+                scale: Utm::<Jgd2011>::SCALE,
+                false_easting: Utm::<Jgd2011>::FALSE_EASTING,
+            },
+            Projection::PlaneRectangular(zone) => Self {
+                zone: format!("{:?}", zone),
+                zone_number: zone.number(),
+                lat0: zone.origin().lat(),
+                lon0: zone.origin().lon(),
+                scale: PlaneRectangular::<Jgd2011>::SCALE,
+                false_easting: 0.,
+            },
+            _ => unreachable!("not a transverse mercator"),
+        }
+    }
+}
+
+/// WKT や PROJJSON から取り出した、座標参照系の識別に用いる要素
+#[derive(Debug, Default)]
+pub(crate) struct Description {
+    pub(crate) projected: bool,
+    pub(crate) epsg: Option<u32>,
+    pub(crate) datum: String,
+    pub(crate) method: String,
+    pub(crate) parameters: Vec<(String, f64)>,
+}
+impl Description {
+    fn from_wkt(root: &Node) -> Option<Self> {
+        let projected = match root.keyword.to_ascii_uppercase().as_str() {
+            "PROJCS" | "PROJCRS" | "PROJECTEDCRS" => true,
+            "GEOGCS" | "GEOGCRS" | "GEOGRAPHICCRS" | "GEODCRS" | "GEODETICCRS" => false,
+            _ => return None,
+        };
+        let epsg = root
+            .children()
+            .filter(|node| node.is(&["AUTHORITY", "ID"]))
+            .find(|node| node.text(0).is_some_and(|a| a.eq_ignore_ascii_case("EPSG")))
+            .and_then(|node| node.text(1)?.parse().ok());
+        let text = |keywords: &[&str]| {
+            let node = root.find(keywords)?;
+            Some(node.text(0)?.to_string())
+        };
+        let mut parameters = Vec::new();
+        root.each(&mut |node| {
+            if node.is(&["PARAMETER"]) {
+                if let (Some(name), Some(value)) = (node.text(0), node.text(1)) {
+                    if let Ok(value) = value.parse() {
+                        parameters.push((name.to_string(), value));
+                    }
+                }
+            }
+        });
+        Some(Self {
+            projected,
+            epsg,
+            datum: text(&["DATUM", "GEODETICDATUM", "TRF", "ENSEMBLE"]).unwrap_or_default(),
+            method: text(&["PROJECTION", "METHOD"]).unwrap_or_default(),
+            parameters,
+        })
+    }
+
+    /// 座標参照系を識別する。
+    pub(crate) fn resolve(&self) -> Option<Crs> {
+        if let Some(crs) = self.epsg.and_then(Crs::from_epsg) {
+            return Some(crs);
+        }
+
+        let method = normalize(&self.method);
+        if self.projected
+            && ["pseudo", "auxiliarysphere", "popular"]
+                .iter()
+                .any(|m| method.contains(m))
+        {
+            // 測地系を区別しない
+            return Crs::from_epsg(WebMercator::<Jgd2011>::EPSG);
+        }
+
+        let datum = normalize(&self.datum);
+        let datum = if datum.contains("tokyo") {
+            Datum::Tokyo
+        } else if !(datum.contains("jgd") || datum.contains("japanesegeodetic")) {
+            return None;
+        } else if datum.contains("2011") {
+            Datum::Jgd2011
+        } else if datum.contains("2000") {
+            Datum::Jgd2000
+        } else {
+            return None;
+        };
+
+        let projection = match self.projected {
+            false => Projection::Geographic,
+            true => self.transverse_mercator()?,
+        };
+        Crs::new(datum, projection)
+    }
+
+    /// 横メルカトル図法のパラメータから、平面直角座標系の系またはUTMの帯を求める
+    fn transverse_mercator(&self) -> Option<Projection> {
+        if !normalize(&self.method).contains("transversemercator") {
+            return None;
+        }
+        let parameter = |matches: &dyn Fn(&str) -> bool| {
+            self.parameters
+                .iter()
+                .find(|(name, _)| matches(&normalize(name)))
+                .map_or(0., |(_, value)| *value)
+        };
+        let lat0 = parameter(&|name| name.contains("latitude"));
+        let lon0 =
+            parameter(&|name| name.contains("centralmeridian") || name.contains("longitude"));
+        let scale = parameter(&|name| name.contains("scale"));
+        let false_easting = parameter(&|name| name.contains("falseeasting"));
+        let eq = |a: f64, b: f64| (a - b).abs() < 1e-6;
+
+        if eq(scale, PlaneRectangular::<Jgd2011>::SCALE) && eq(false_easting, 0.) {
+            (1..=19)
+                .filter_map(Zone::new)
+                .find(|zone| eq(zone.origin().lat(), lat0) && eq(zone.origin().lon(), lon0))
+                .map(Projection::PlaneRectangular)
+        } else if eq(scale, Utm::<Jgd2011>::SCALE)
+            && eq(false_easting, Utm::<Jgd2011>::FALSE_EASTING)
+            && eq(lat0, 0.)
+        {
+            let zone = (lon0 + 183.) / 6.;
+            (eq(zone, zone.round()) && (1. ..=60.).contains(&zone))
+                .then(|| Projection::Utm(zone.round() as u8))
+        } else {
+            None
+        }
+    }
+}
+
+/// 英数字のみの小文字にする
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// WKT の要素。`KEYWORD[引数, ...]`
+#[derive(Debug)]
+struct Node {
+    keyword: String,
+    args: Vec<Arg>,
+}
+#[derive(Debug)]
+enum Arg {
+    /// 引用符で囲まれた文字列、数値、または列挙値
+    Text(String),
+    Node(Node),
+}
+impl Node {
+    fn is(&self, keywords: &[&str]) -> bool {
+        keywords
+            .iter()
+            .any(|k| self.keyword.eq_ignore_ascii_case(k))
+    }
+
+    fn text(&self, index: usize) -> Option<&str> {
+        match self.args.get(index)? {
+            Arg::Text(text) => Some(text),
+            Arg::Node(_) => None,
+        }
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Node> {
+        self.args.iter().filter_map(|arg| match arg {
+            Arg::Node(node) => Some(node),
+            Arg::Text(_) => None,
+        })
+    }
+
+    /// 深さ優先で最初に見つかった子孫
+    fn find(&self, keywords: &[&str]) -> Option<&Node> {
+        self.children().find_map(|node| match node.is(keywords) {
+            true => Some(node),
+            false => node.find(keywords),
+        })
+    }
+
+    /// 自身とすべての子孫
+    fn each<'a>(&'a self, f: &mut impl FnMut(&'a Node)) {
+        f(self);
+        for node in self.children() {
+            node.each(f);
+        }
+    }
+}
+
+fn parse_arg(chars: &mut Peekable<Chars>) -> Option<Arg> {
+    skip_whitespace(chars);
+    if chars.next_if_eq(&'"').is_some() {
+        let mut text = String::new();
+        loop {
+            match chars.next()? {
+                // 引用符は2つ重ねてエスケープされる
+                '"' if chars.next_if_eq(&'"').is_none() => break,
+                c => text.push(c),
+            }
+        }
+        skip_whitespace(chars);
+        return Some(Arg::Text(text));
+    }
+
+    let mut token = String::new();
+    while let Some(c) = chars.next_if(|c| !matches!(c, ',' | '[' | ']' | '(' | ')')) {
+        token.push(c);
+    }
+    let token = token.trim().to_string();
+    if chars.next_if(|c| matches!(c, '[' | '(')).is_none() {
+        return Some(Arg::Text(token));
+    }
+
+    let mut args = Vec::new();
+    loop {
+        skip_whitespace(chars);
+        if chars.next_if(|c| matches!(c, ']' | ')')).is_some() {
+            break;
+        }
+        args.push(parse_arg(chars)?);
+        skip_whitespace(chars);
+        match chars.next()? {
+            ',' => continue,
+            ']' | ')' => break,
+            _ => return None,
+        }
+    }
+    skip_whitespace(chars);
+    Some(Arg::Node(Node {
+        keyword: token,
+        args,
+    }))
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for code in (0..40000).filter_map(Crs::from_epsg) {
+            assert_eq!(
+                Crs::from_wkt(&code.to_wkt1()),
+                Some(code),
+                "{}",
+                code.to_wkt1()
+            );
+            assert_eq!(
+                Crs::from_wkt(&code.to_wkt2()),
+                Some(code),
+                "{}",
+                code.to_wkt2()
+            );
+        }
+    }
+
+    #[test]
+    fn esri() {
+        let prj = Crs::from_epsg(6677).unwrap().to_wkt1();
+        assert_eq!(
+            prj,
+            r#"PROJCS["JGD_2011_Japan_Zone_9",GEOGCS["GCS_JGD_2011",DATUM["D_JGD_2011",SPHEROID["GRS_1980",6378137.0,298.257222101]],PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]],PROJECTION["Transverse_Mercator"],PARAMETER["False_Easting",0.0],PARAMETER["False_Northing",0.0],PARAMETER["Central_Meridian",139.83333333333334],PARAMETER["Scale_Factor",0.9999],PARAMETER["Latitude_Of_Origin",36.0],UNIT["Meter",1.0]]"#
+        );
+    }
+
+    #[test]
+    fn wkt2() {
+        let wkt = Crs::from_epsg(6668).unwrap().to_wkt2();
+        assert_eq!(
+            wkt,
+            r#"GEOGCRS["JGD2011",DATUM["Japanese Geodetic Datum 2011",ELLIPSOID["GRS 1980",6378137,298.257222101,LENGTHUNIT["metre",1]]],PRIMEM["Greenwich",0,ANGLEUNIT["degree",0.0174532925199433]],CS[ellipsoidal,2],AXIS["geodetic latitude (Lat)",north,ORDER[1],ANGLEUNIT["degree",0.0174532925199433]],AXIS["geodetic longitude (Lon)",east,ORDER[2],ANGLEUNIT["degree",0.0174532925199433]],ID["EPSG",6668]]"#
+        );
+    }
+
+    /// GDAL が出力する WKT1
+    #[test]
+    fn gdal() {
+        let wkt = r#"PROJCS["JGD2000 / Japan Plane Rectangular CS IX",
+            GEOGCS["JGD2000",
+                DATUM["Japanese_Geodetic_Datum_2000",
+                    SPHEROID["GRS 1980",6378137,298.257222101,AUTHORITY["EPSG","7019"]],
+                    TOWGS84[0,0,0,0,0,0,0],
+                    AUTHORITY["EPSG","6612"]],
+                PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],
+                UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],
+                AUTHORITY["EPSG","4612"]],
+            PROJECTION["Transverse_Mercator"],
+            PARAMETER["latitude_of_origin",36],
+            PARAMETER["central_meridian",139.833333333333],
+            PARAMETER["scale_factor",0.9999],
+            PARAMETER["false_easting",0],
+            PARAMETER["false_northing",0],
+            UNIT["metre",1,AUTHORITY["EPSG","9001"]],
+            AXIS["Northing",NORTH],
+            AXIS["Easting",EAST],
+            AUTHORITY["EPSG","2451"]]"#;
+        assert_eq!(Crs::from_wkt(wkt), Crs::from_epsg(2451));
+
+        // EPSG コードなし
+        let wkt = wkt.replace(r#"AUTHORITY["EPSG","2451"]"#, r#"AUTHORITY["X","1"]"#);
+        assert_eq!(Crs::from_wkt(&wkt), Crs::from_epsg(2451));
+    }
+
+    #[test]
+    fn unknown() {
+        for wkt in [
+            "",
+            "GEOGCS",
+            r#"GEOGCS["GCS_WGS_1984",DATUM["D_WGS_1984",SPHEROID["WGS_1984",6378137.0,298.257223563]]]"#,
+            r#"GEOGCS["GCS_JGD_2011",DATUM["D_JGD_2011"]"#,
+            r#"PROJCS["x",GEOGCS["GCS_JGD_2011",DATUM["D_JGD_2011"]],PROJECTION["Transverse_Mercator"],PARAMETER["Scale_Factor",0.9999]]"#,
+        ] {
+            assert_eq!(Crs::from_wkt(wkt), None, "{wkt}");
+        }
+    }
+}
@@ -27,3 +27,142 @@ fn iwaki_2() {
     assert_abs_diff_eq!(lat, 37.093698, epsilon = MM_IN_DEGREES);
     assert_abs_diff_eq!(lon, 140.829111, epsilon = MM_IN_DEGREES);
 }
+
+#[test]
+fn method() {
+    use jgd::{Interpolation, Method};
+
+    let (_, method) =
+        jgd::from_jgd2000(38.26, 140.87).to_jgd2011_with_method(Interpolation::Bilinear);
+    assert_eq!(method, Method::Bilinear);
+
+    let jgd2000 = jgd::from_jgd2000(37.093698, 140.829111);
+    let (jgd2011, method) = jgd2000.to_jgd2011_with_method(Interpolation::Bilinear);
+    assert_eq!(method, Method::Identity);
+    assert_eq!(method.accuracy(), None);
+    assert_eq!(jgd2011.degrees(), jgd2000.degrees());
+}
+
+#[test]
+fn try_transform() {
+    let jgd2000 = jgd::from_jgd2000(38.26, 140.87);
+    let jgd2011 = jgd2000.try_to_jgd2011().unwrap();
+    assert_eq!(jgd2011.degrees(), jgd2000.to_jgd2011().degrees());
+    let ret = jgd2011.try_to_jgd2000().unwrap();
+    assert_eq!(ret.degrees(), jgd2011.to_jgd2000().degrees());
+
+    // パラメータグリッドがない地域はエラーとしない
+    let jgd2000 = jgd::from_jgd2000(37.093698, 140.829111);
+    let jgd2011 = jgd2000.try_to_jgd2011().unwrap();
+    assert_eq!(jgd2011.degrees(), jgd2000.degrees());
+    let ret = jgd::from_jgd2011(37.093698, 140.829111)
+        .try_to_jgd2000()
+        .unwrap();
+    assert_eq!(ret.degrees(), jgd2000.degrees());
+
+    let err = jgd::from_jgd2000(f64::NAN, 140.87)
+        .try_to_jgd2011()
+        .unwrap_err();
+    assert!(matches!(err, jgd::Error::InvalidCoordinate(_)));
+    let err = jgd::from_jgd2011(-90.5, 140.87)
+        .try_to_jgd2000()
+        .unwrap_err();
+    assert!(matches!(err, jgd::Error::InvalidCoordinate(_)));
+}
+
+/// パラメータグリッドがある地域とない地域をまたがる形状
+#[test]
+fn shape() {
+    use jgd::{Method, Shape, Strategy};
+
+    let shape = [
+        jgd::from_jgd2000(37.090536, 140.840350),
+        jgd::from_jgd2000(37.093698, 140.829111),
+    ];
+    let sut = Shape::from_jgd2000(&shape, Strategy::default());
+    assert!(sut.straddles());
+    assert_eq!(sut.method(), Method::Identity);
+    for (ret, p) in sut.points().iter().zip(&shape) {
+        assert_eq!(ret.degrees(), p.degrees());
+    }
+}
+
+#[test]
+fn sendai_inverse() {
+    let (lat, lon) = jgd::from_jgd2011(38.259991997, 140.870036378)
+        .to_jgd2000()
+        .into();
+    assert_abs_diff_eq!(lat, 38.26, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.87, epsilon = MM_IN_DEGREES);
+}
+
+#[test]
+fn iwaki_1_inverse() {
+    let (lat, lon) = jgd::from_jgd2011(37.090532997, 140.840375142)
+        .to_jgd2000()
+        .into();
+    assert_abs_diff_eq!(lat, 37.090536, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.840350, epsilon = MM_IN_DEGREES);
+}
+
+/// パラメータグリッドがない地域
+#[test]
+fn iwaki_2_inverse() {
+    let (lat, lon) = jgd::from_jgd2011(37.093698, 140.829111).to_jgd2000().into();
+    assert_abs_diff_eq!(lat, 37.093698, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.829111, epsilon = MM_IN_DEGREES);
+}
+
+#[test]
+fn sendai_3d() {
+    let (lat, lon, height) = jgd::from_jgd2000_3d(38.26, 140.87, 100.)
+        .to_jgd2011_3d()
+        .into();
+    assert_abs_diff_eq!(lat, 38.259991997, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.870036378, epsilon = MM_IN_DEGREES);
+    assert_eq!(height, 100.);
+}
+
+#[test]
+fn sendai_3d_inverse() {
+    let jgd2011 = jgd::from_jgd2000_3d(38.26, 140.87, 100.).to_jgd2011_3d();
+    let (lat, lon, height) = jgd2011.to_jgd2000_3d().into();
+    assert_abs_diff_eq!(lat, 38.26, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.87, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(height, 100., epsilon = 0.001);
+}
+
+/// 仙台付近の1つの3次メッシュ (57403619) で、一様に10cm隆起させる
+fn uplift() -> jgd::GridBuf<jgd::MicroMeter> {
+    let par = "\
+MeshCode   dH(m)
+57403619     0.100
+57403629     0.100
+57403710     0.100
+57403720     0.100
+";
+    jgd::GridBuf::from_par(par.as_bytes()).unwrap()
+}
+
+#[test]
+fn sendai_3d_with_height() {
+    let height = uplift();
+    let (lat, lon, h) = jgd::from_jgd2000_3d(38.26, 140.87, 100.)
+        .to_jgd2011_3d_with(&height.as_grid())
+        .into();
+    assert_abs_diff_eq!(lat, 38.259991997, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.870036378, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(h, 100.1, epsilon = 1e-9);
+
+    let jgd2011 = jgd::from_jgd2011_3d(lat, lon, h);
+    let (lat, lon, h) = jgd2011.to_jgd2000_3d_with(&height.as_grid()).into();
+    assert_abs_diff_eq!(lat, 38.26, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.87, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(h, 100., epsilon = 1e-9);
+
+    // 標高補正パラメータが存在しない地域
+    let (_, _, h) = jgd::from_jgd2000_3d(37.09, 140.84, 100.)
+        .to_jgd2011_3d_with(&height.as_grid())
+        .into();
+    assert_eq!(h, 100.);
+}
@@ -1,10 +1,14 @@
 //! projの実装と比較するテスト。
 
 use approx::assert_abs_diff_eq;
+use jgd::{Jgd2000, Jgd2011, PlaneRectangular, Tokyo, Utm, Zone};
 
 /// 許容誤差: ±1mm
 const MM_IN_DEGREES: f64 = 0.000000009;
 
+/// 許容誤差: ±1mm
+const MM: f64 = 0.001;
+
 #[test]
 fn towgs84() {
     let (lat, lon) = jgd::from_tokyo97(35., 135.).to_jgd2000().into();
@@ -18,3 +22,158 @@ fn towgs84_inverse() {
     assert_abs_diff_eq!(lat, 34.99680236, epsilon = MM_IN_DEGREES);
     assert_abs_diff_eq!(lon, 135.00279591, epsilon = MM_IN_DEGREES);
 }
+
+#[test]
+fn towgs84_3d() {
+    for ((lat, lon, height), expected) in [
+        ((35., 135., 0.), (35.0031971798, 134.9972042490, 50.9910)),
+        (
+            (35., 135., 1000.),
+            (35.0031966769, 134.9972046868, 1050.9910),
+        ),
+        (
+            (36., 138.7, 3776.),
+            (36.0031726173, 138.6968462698, 3818.9687),
+        ),
+    ] {
+        let ret = jgd::from_tokyo97_3d(lat, lon, height)
+            .to_jgd2000_3d()
+            .into();
+        assert_3d(ret, expected);
+    }
+}
+
+#[test]
+fn towgs84_3d_inverse() {
+    for ((lat, lon, height), expected) in [
+        ((35., 135., 0.), (34.9968023647, 135.0027959117, -50.9611)),
+        (
+            (35., 135., 1000.),
+            (34.9968028677, 135.0027954738, 949.0389),
+        ),
+        (
+            (36., 138.7, 3776.),
+            (35.9968269183, 138.7031538965, 3733.0634),
+        ),
+    ] {
+        let ret = jgd::from_jgd2000_3d(lat, lon, height)
+            .to_tokyo97_3d()
+            .into();
+        assert_3d(ret, expected);
+    }
+}
+
+fn assert_3d(actual: (f64, f64, f64), expected: (f64, f64, f64)) {
+    assert_abs_diff_eq!(actual.0, expected.0, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(actual.1, expected.1, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(actual.2, expected.2, epsilon = MM);
+}
+
+#[test]
+fn plane_rectangular_ix() {
+    let (x, y) = jgd::from_jgd2011(36.103774791666666, 140.08785504166667)
+        .to_plane_rectangular(Zone::IX)
+        .into();
+    assert_abs_diff_eq!(x, 11543.6883, epsilon = MM);
+    assert_abs_diff_eq!(y, 22916.2436, epsilon = MM);
+}
+
+#[test]
+fn plane_rectangular_ix_inverse() {
+    let (lat, lon) = PlaneRectangular::<Jgd2011>::new(Zone::IX, 11543.6883, 22916.2436)
+        .to_geographic()
+        .into();
+    assert_abs_diff_eq!(lat, 36.103774791666666, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.08785504166667, epsilon = MM_IN_DEGREES);
+}
+
+#[test]
+fn plane_rectangular_i() {
+    let (x, y) = jgd::from_jgd2000(33.5, 130.3)
+        .to_plane_rectangular(Zone::I)
+        .into();
+    assert_abs_diff_eq!(x, 55735.3419, epsilon = MM);
+    assert_abs_diff_eq!(y, 74331.5527, epsilon = MM);
+
+    let (lat, lon) = PlaneRectangular::<Jgd2000>::new(Zone::I, x, y)
+        .to_geographic()
+        .into();
+    assert_abs_diff_eq!(lat, 33.5, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 130.3, epsilon = MM_IN_DEGREES);
+}
+
+#[test]
+fn plane_rectangular_xiii() {
+    let (x, y) = jgd::from_jgd2011(43.0, 145.5)
+        .to_plane_rectangular(Zone::XIII)
+        .into();
+    assert_abs_diff_eq!(x, -110333.1095, epsilon = MM);
+    assert_abs_diff_eq!(y, 101916.5961, epsilon = MM);
+}
+
+#[test]
+fn plane_rectangular_xv() {
+    let (x, y) = jgd::from_jgd2011(26.2, 127.7)
+        .to_plane_rectangular(Zone::XV)
+        .into();
+    assert_abs_diff_eq!(x, 22171.0916, epsilon = MM);
+    assert_abs_diff_eq!(y, 19987.5416, epsilon = MM);
+}
+
+/// Bessel楕円体
+#[test]
+fn plane_rectangular_tokyo() {
+    let (x, y) = jgd::from_tokyo(35.7, 139.7)
+        .to_plane_rectangular(Zone::IX)
+        .into();
+    assert_abs_diff_eq!(x, -33271.8129, epsilon = MM);
+    assert_abs_diff_eq!(y, -12064.5478, epsilon = MM);
+
+    let (x, y) = jgd::from_tokyo(41.5, 140.1)
+        .to_plane_rectangular(Zone::X)
+        .into();
+    assert_abs_diff_eq!(x, 166798.4713, epsilon = MM);
+    assert_abs_diff_eq!(y, -61217.2363, epsilon = MM);
+
+    let (lat, lon) = PlaneRectangular::<Tokyo>::new(Zone::X, x, y)
+        .to_geographic()
+        .into();
+    assert_abs_diff_eq!(lat, 41.5, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 140.1, epsilon = MM_IN_DEGREES);
+}
+
+#[test]
+fn utm() {
+    let utm = jgd::from_jgd2011(35.681236, 139.767125).to_utm();
+    assert_eq!(utm.zone(), 54);
+    assert_abs_diff_eq!(utm.easting(), 388435.6871, epsilon = MM);
+    assert_abs_diff_eq!(utm.northing(), 3949293.9780, epsilon = MM);
+
+    let (lat, lon) = Utm::<Jgd2011>::new(54, 388435.6871, 3949293.9780)
+        .to_geographic()
+        .into();
+    assert_abs_diff_eq!(lat, 35.681236, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 139.767125, epsilon = MM_IN_DEGREES);
+}
+
+#[test]
+fn utm_51() {
+    let (e, n) = jgd::from_jgd2000(24.34, 124.16).to_utm().into();
+    assert_abs_diff_eq!(e, 617678.1433, epsilon = MM);
+    assert_abs_diff_eq!(n, 2692361.1246, epsilon = MM);
+
+    let (lat, lon) = Utm::<Jgd2000>::new(51, e, n).to_geographic().into();
+    assert_abs_diff_eq!(lat, 24.34, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon, 124.16, epsilon = MM_IN_DEGREES);
+}
+
+#[test]
+fn utm_zone() {
+    let (e, n) = jgd::from_jgd2011(34.7, 135.5).to_utm_zone(53).into();
+    assert_abs_diff_eq!(e, 545791.9299, epsilon = MM);
+    assert_abs_diff_eq!(n, 3839888.7741, epsilon = MM);
+
+    let (e, n) = jgd::from_jgd2011(43.3, 145.6).to_utm_zone(55).into();
+    assert_abs_diff_eq!(e, 386444.3530, epsilon = MM);
+    assert_abs_diff_eq!(n, 4795081.7264, epsilon = MM);
+}
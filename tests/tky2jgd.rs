@@ -2,7 +2,7 @@
 #![cfg(feature = "tky2jgd")]
 
 use approx::assert_abs_diff_eq;
-use jgd::{LatLon, Tokyo};
+use jgd::{Error, Interpolation, Jgd2000, LatLon, Method, Shape, Strategy, Tokyo};
 
 /// 許容誤差: ±1mm
 const MM_IN_DEGREES: f64 = 0.000000009;
@@ -12,6 +12,15 @@ fn assert_tky2jgd(tokyo: LatLon, expected: LatLon) {
     let (lat1, lon1) = Tokyo::new(tokyo).to_jgd2000().degrees().into();
     assert_abs_diff_eq!(lat0, lat1, epsilon = MM_IN_DEGREES);
     assert_abs_diff_eq!(lon0, lon1, epsilon = MM_IN_DEGREES);
+
+    assert_jgd2tky(expected, tokyo, MM_IN_DEGREES);
+}
+
+fn assert_jgd2tky(jgd2000: LatLon, expected: LatLon, epsilon: f64) {
+    let (lat0, lon0) = expected.into();
+    let (lat1, lon1) = Jgd2000::new(jgd2000).to_tokyo().degrees().into();
+    assert_abs_diff_eq!(lat0, lat1, epsilon = epsilon);
+    assert_abs_diff_eq!(lon0, lon1, epsilon = epsilon);
 }
 
 #[test]
@@ -67,5 +76,162 @@ fn 前浜() {
 fn 海上() {
     let tokyo = LatLon::from_dms((36, 18, 35.99000), (143, 00, 00.00000));
     let expected = LatLon::from_dms((36, 18, 47.72512), (142, 59, 47.29009));
-    assert_tky2jgd(tokyo, expected);
+    let (lat0, lon0) = expected.into();
+    let (lat1, lon1) = Tokyo::new(tokyo).to_jgd2000().degrees().into();
+    assert_abs_diff_eq!(lat0, lat1, epsilon = MM_IN_DEGREES);
+    assert_abs_diff_eq!(lon0, lon1, epsilon = MM_IN_DEGREES);
+
+    // 3パラメータによる変換は楕円体高を捨てるため、往復すると1mm程度ずれる
+    assert_jgd2tky(expected, tokyo, 2. * MM_IN_DEGREES);
+}
+
+/// 離島のパラメータグリッドがない地域
+#[test]
+fn 南大東島() {
+    // 5m
+    const EPSILON: f64 = 0.00005;
+
+    let inside = LatLon::from_dms((25, 52, 15.0), (131, 15, 22.5));
+    let coast = LatLon::from_dms((25, 48, 45.0), (131, 16, 7.5));
+    let (lat0, lon0) = (Tokyo::new(inside).to_jgd2000().degrees() - inside).into();
+    let (lat1, lon1) = (Tokyo::new(coast).to_jgd2000().degrees() - coast).into();
+    assert_abs_diff_eq!(lat0, lat1, epsilon = EPSILON);
+    assert_abs_diff_eq!(lon0, lon1, epsilon = EPSILON);
+
+    let jgd2000 = Tokyo::new(coast).to_jgd2000().degrees();
+    assert_jgd2tky(jgd2000, coast, 2. * MM_IN_DEGREES);
+}
+
+/// 3次メッシュの四隅の一部しかパラメータが存在しない沿岸部
+#[test]
+fn 横浜沿岸() {
+    // 5cm
+    const EPSILON: f64 = 0.0000005;
+
+    let inside = LatLon::from_dms((35, 27, 0.0), (139, 42, 40.32));
+    let coast = LatLon::from_dms((35, 27, 0.0), (139, 42, 46.08));
+    let (lat0, lon0) = (Tokyo::new(inside).to_jgd2000().degrees() - inside).into();
+
+    // 3パラメータにフォールバックすると、20cm程度の不連続が生じる
+    let (lat1, _) = (Tokyo::new(coast).to_jgd2000().degrees() - coast).into();
+    assert!((lat0 - lat1).abs() > EPSILON);
+
+    let (lat2, lon2) = (Tokyo::new(coast).to_jgd2000_partial().degrees() - coast).into();
+    assert_abs_diff_eq!(lat0, lat2, epsilon = EPSILON);
+    assert_abs_diff_eq!(lon0, lon2, epsilon = EPSILON);
+
+    let (lat3, lon3) = (Tokyo::new(coast).to_jgd2000_nearest(1000.).degrees() - coast).into();
+    assert_abs_diff_eq!(lat0, lat3, epsilon = EPSILON);
+    assert_abs_diff_eq!(lon0, lon3, epsilon = EPSILON);
+
+    // 範囲内に格子点がなければ、フォールバックされる
+    let fallback = Tokyo::new(coast).to_jgd2000_nearest(1.).degrees();
+    assert_eq!(fallback, Tokyo::new(coast).to_jgd2000().degrees());
+}
+
+#[test]
+fn method() {
+    let inside = LatLon::from_dms((35, 27, 0.0), (139, 42, 40.32));
+    let coast = LatLon::from_dms((35, 27, 0.0), (139, 42, 46.08));
+    let sea = LatLon::from_dms((36, 18, 35.99000), (143, 00, 00.00000));
+    let method = |p, interpolation| Tokyo::new(p).to_jgd2000_with_method(interpolation).1;
+
+    assert_eq!(method(inside, Interpolation::Bilinear), Method::Bilinear);
+    assert_eq!(method(inside, Interpolation::Partial), Method::Bilinear);
+    assert_eq!(
+        method(coast, Interpolation::Bilinear),
+        Method::ThreeParameter
+    );
+    assert_eq!(method(coast, Interpolation::Partial), Method::Partial);
+    let nearest = Interpolation::Nearest { limit: 1000. };
+    assert_eq!(method(coast, nearest), Method::Nearest);
+    assert_eq!(method(sea, nearest), Method::ThreeParameter);
+
+    let (jgd2000, _) = Tokyo::new(coast).to_jgd2000_with_method(Interpolation::Partial);
+    assert_eq!(
+        jgd2000.degrees(),
+        Tokyo::new(coast).to_jgd2000_partial().degrees()
+    );
+}
+
+#[test]
+fn try_transform() {
+    let tokyo = LatLon::from_dms((36, 27, 39.20500), (140, 35, 06.11100));
+    let jgd2000 = Tokyo::new(tokyo).try_to_jgd2000().unwrap();
+    assert_eq!(jgd2000.degrees(), Tokyo::new(tokyo).to_jgd2000().degrees());
+    let ret = jgd2000.try_to_tokyo().unwrap();
+    assert_eq!(ret.degrees(), jgd2000.to_tokyo().degrees());
+
+    let sea = LatLon::from_dms((36, 18, 35.99000), (143, 00, 00.00000));
+    assert_eq!(
+        Tokyo::new(sea).try_to_jgd2000().unwrap_err(),
+        Error::OutsideGrid(sea)
+    );
+    assert_eq!(
+        Jgd2000::new(sea).try_to_tokyo().unwrap_err(),
+        Error::OutsideGrid(sea)
+    );
+    // 3パラメータによる変換は範囲外でもエラーとならない
+    assert!(Jgd2000::new(sea).try_to_tokyo97().is_ok());
+
+    let coast = LatLon::from_dms((35, 27, 0.0), (139, 42, 46.08));
+    assert!(Tokyo::new(coast).try_to_jgd2000().is_err());
+    assert!(Tokyo::new(coast).try_to_jgd2000_partial().is_ok());
+    assert!(Tokyo::new(coast).try_to_jgd2000_nearest(1000.).is_ok());
+}
+
+#[test]
+fn try_invalid() {
+    for (lat, lon) in [(f64::NAN, 140.), (36., f64::INFINITY), (91., 140.)] {
+        let err = jgd::from_tokyo(lat, lon).try_to_jgd2000().unwrap_err();
+        assert!(matches!(err, Error::InvalidCoordinate(_)));
+        let err = jgd::from_jgd2000(lat, lon).try_to_tokyo().unwrap_err();
+        assert!(matches!(err, Error::InvalidCoordinate(_)));
+    }
+    let err = jgd::from_tokyo_3d(36., 140., f64::NAN)
+        .try_to_jgd2000_3d()
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidCoordinate(_)));
+}
+
+/// 沿岸部をまたがる形状
+#[test]
+fn shape() {
+    let inside = LatLon::from_dms((35, 27, 0.0), (139, 42, 40.32));
+    let coast = LatLon::from_dms((35, 27, 0.0), (139, 42, 46.08));
+    let shape = [Tokyo::new(inside), Tokyo::new(coast)];
+    let degrees = |shape: &Shape<Jgd2000>| {
+        shape
+            .points()
+            .iter()
+            .map(Jgd2000::degrees)
+            .collect::<Vec<_>>()
+    };
+
+    // 一部の点を補間できないため、すべての点がフォールバックされる
+    let sut = Shape::from_tokyo(&shape, Strategy::default());
+    assert!(sut.straddles());
+    assert_eq!(sut.method(), Method::ThreeParameter);
+    let fallback = degrees(&sut);
+    assert_eq!(fallback[1], Tokyo::new(coast).to_jgd2000().degrees());
+    assert_ne!(fallback[0], Tokyo::new(inside).to_jgd2000().degrees());
+
+    let sut = Shape::from_tokyo(&shape, Strategy::Grid(Interpolation::Partial));
+    assert!(sut.straddles());
+    assert_eq!(sut.method(), Method::Partial);
+    assert_eq!(
+        degrees(&sut),
+        [
+            Tokyo::new(inside).to_jgd2000().degrees(),
+            Tokyo::new(coast).to_jgd2000_partial().degrees(),
+        ]
+    );
+
+    let sut = Shape::from_tokyo(&shape, Strategy::Fallback);
+    assert_eq!(sut.method(), Method::ThreeParameter);
+    assert_eq!(degrees(&sut), fallback);
+
+    let sut = Shape::from_tokyo(&shape[..1], Strategy::default());
+    assert!(!sut.straddles());
+    assert_eq!(sut.method(), Method::Bilinear);
 }